@@ -0,0 +1,159 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+use serde_yaml::{from_str, Mapping, Value};
+
+/// File inside a day directory with the correct answers of the challenge
+pub const ANSWERS_FILE: &str = "answers.yml";
+
+/// Error type for the recorded answers
+#[derive(Debug)]
+pub enum AnswersError {
+    IoError(io::Error),
+    YamlError(serde_yaml::Error),
+    BadFormat(String),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::IoError(_) => write!(f, "Unable to access the {} file", ANSWERS_FILE),
+            AnswersError::YamlError(_) => write!(f, "The {} file is not valid yaml", ANSWERS_FILE),
+            AnswersError::BadFormat(m) => write!(f, "Bad format! {}", m),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnswersError::IoError(e) => Some(e),
+            AnswersError::YamlError(e) => Some(e),
+            AnswersError::BadFormat(_) => None,
+        }
+    }
+}
+
+/// Correct answers recorded for a day
+///
+/// ```yml
+/// part1: 24000
+/// part2: 45000
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    /// Reads the answers of the day directory
+    /// If the file does not exist, no answers are recorded
+    pub fn load(day_path: &Path) -> Result<Self, AnswersError> {
+        let path = day_path.join(ANSWERS_FILE);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(AnswersError::IoError)?;
+        Self::parse(&content)
+    }
+
+    /// Parses the content of an answers file
+    pub fn parse(content: &str) -> Result<Self, AnswersError> {
+        let value: Value = from_str(content).map_err(AnswersError::YamlError)?;
+
+        let mapping = match value {
+            Value::Null => return Ok(Self::default()),
+            Value::Mapping(m) => m,
+            _ => {
+                return Err(AnswersError::BadFormat(
+                    "answers must be a map with part1 and part2. Check your format".into(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            part1: scalar_to_string(mapping.get(Value::String("part1".into())))?,
+            part2: scalar_to_string(mapping.get(Value::String("part2".into())))?,
+        })
+    }
+
+    /// Writes the answers into the day directory
+    pub fn save(&self, day_path: &Path) -> Result<(), AnswersError> {
+        let mut mapping = Mapping::new();
+
+        for (key, value) in [("part1", &self.part1), ("part2", &self.part2)] {
+            if let Some(value) = value {
+                mapping.insert(key.into(), Value::String(value.clone()));
+            }
+        }
+
+        let content = serde_yaml::to_string(&mapping).map_err(AnswersError::YamlError)?;
+        fs::write(day_path.join(ANSWERS_FILE), content).map_err(AnswersError::IoError)
+    }
+
+    /// Answer of the part (1 or 2)
+    pub fn part(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Answers can be written as numbers or strings in the yml file
+fn scalar_to_string(value: Option<&Value>) -> Result<Option<String>, AnswersError> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.trim().to_string())),
+        Some(Value::Number(n)) => Ok(Some(n.to_string())),
+        Some(Value::Bool(b)) => Ok(Some(b.to_string())),
+        Some(_) => Err(AnswersError::BadFormat(
+            "Each answer must be a single value. Check your format".into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("part1: 24000\npart2: ' abc '\n").unwrap();
+        assert_eq!(answers.part1, Some("24000".to_string()));
+        assert_eq!(answers.part2, Some("abc".to_string()));
+
+        let answers = Answers::parse("part1: 7").unwrap();
+        assert_eq!(answers.part2, None);
+
+        assert_eq!(Answers::parse("").unwrap(), Answers::default());
+        assert!(matches!(
+            Answers::parse("- 1\n- 2"),
+            Err(AnswersError::BadFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_save_and_load_answers() {
+        let tmp = TempDir::new("testing_answers").unwrap();
+
+        assert_eq!(Answers::load(tmp.path()).unwrap(), Answers::default());
+
+        let answers = Answers {
+            part1: Some("123".to_string()),
+            part2: None,
+        };
+        answers.save(tmp.path()).unwrap();
+
+        assert_eq!(Answers::load(tmp.path()).unwrap(), answers);
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{index::DayEntry, registry::TemplateRegistry, utils::run_shell_command};
+
+/// Wall-clock timings of the runs of a day, in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayTiming {
+    pub day: u8,
+    pub title: String,
+    pub runs: usize,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Timings of every benchmarked day of a year in a language
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimingTable {
+    pub year: u16,
+    pub language: String,
+    pub days: Vec<DayTiming>,
+}
+
+impl DayTiming {
+    /// Summarizes the durations of the runs of a day
+    pub fn from_durations(day: u8, title: &str, durations: &[Duration]) -> Self {
+        let mut ms = durations
+            .iter()
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .collect::<Vec<f64>>();
+        ms.sort_by(|a, b| a.total_cmp(b));
+
+        let median = match ms.len() {
+            0 => 0.0,
+            n if n % 2 == 0 => (ms[n / 2 - 1] + ms[n / 2]) / 2.0,
+            n => ms[n / 2],
+        };
+
+        Self {
+            day,
+            title: title.to_string(),
+            runs: ms.len(),
+            min_ms: ms.first().copied().unwrap_or_default(),
+            median_ms: median,
+            max_ms: ms.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+impl TimingTable {
+    /// Name of the files of the table, without extension
+    pub fn file_stem(&self) -> String {
+        format!("bench-{}-{}", self.year, self.language)
+    }
+
+    /// Renders the table as markdown. When a baseline is given, the change of
+    /// the median of every day is added
+    pub fn to_markdown(&self, baseline: Option<&TimingTable>) -> String {
+        let mut out = format!("# {} {}\n\n", self.year, self.language);
+
+        if baseline.is_some() {
+            out.push_str("| Day | Title | Runs | Min (ms) | Median (ms) | Max (ms) | Baseline (ms) | Change |\n");
+            out.push_str("| --- | ----- | ---- | -------- | ----------- | -------- | ------------- | ------ |\n");
+        } else {
+            out.push_str("| Day | Title | Runs | Min (ms) | Median (ms) | Max (ms) |\n");
+            out.push_str("| --- | ----- | ---- | -------- | ----------- | -------- |\n");
+        }
+
+        for d in &self.days {
+            out.push_str(&format!(
+                "| {:02} | {} | {} | {:.3} | {:.3} | {:.3} |",
+                d.day, d.title, d.runs, d.min_ms, d.median_ms, d.max_ms
+            ));
+
+            if let Some(baseline) = baseline {
+                match baseline.days.iter().find(|b| b.day == d.day) {
+                    Some(b) if b.median_ms > 0.0 => out.push_str(&format!(
+                        " {:.3} | {:+.1}% |",
+                        b.median_ms,
+                        (d.median_ms - b.median_ms) / b.median_ms * 100.0
+                    )),
+                    Some(b) => out.push_str(&format!(" {:.3} | - |", b.median_ms)),
+                    None => out.push_str(" - | - |"),
+                }
+            }
+
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// Runs the `run` command of a day `runs` times and measures each run
+pub fn time_day(
+    entry: &DayEntry,
+    registry: &TemplateRegistry,
+    runs: usize,
+) -> Result<DayTiming, String> {
+    let template = registry
+        .template_for_challenge(&entry.challenge())
+        .map_err(|e| format!("Unable to read the template: {}", e))?;
+
+    let run = template
+        .run
+        .ok_or_else(|| format!("No run command for {}", entry.language))?;
+
+    let mut durations = Vec::with_capacity(runs);
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let output = run_shell_command(&run.command, &entry.path)
+            .map_err(|e| format!("{} failed to execute: {}", run.command, e))?;
+        durations.push(start.elapsed());
+
+        if !output.status.success() {
+            return Err(format!(
+                "{} failed to execute\n\n{}",
+                run.command,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+    }
+
+    Ok(DayTiming::from_durations(
+        entry.day,
+        &entry.title,
+        &durations,
+    ))
+}
+
+/// Groups the timings of the days by year and language
+/// Expects the days to be sorted, as returned by `scan_days`
+pub fn group_timings(timings: Vec<(DayEntry, DayTiming)>) -> Vec<TimingTable> {
+    let mut tables: Vec<TimingTable> = vec![];
+
+    for (entry, timing) in timings {
+        match tables.last_mut() {
+            Some(t) if t.year == entry.year && t.language == entry.language => t.days.push(timing),
+            _ => tables.push(TimingTable {
+                year: entry.year,
+                language: entry.language,
+                days: vec![timing],
+            }),
+        }
+    }
+
+    tables
+}
+
+/// Reads a table previously written with `write_table`
+pub fn read_table(dir: &Path, file_stem: &str) -> io::Result<Option<TimingTable>> {
+    let path = dir.join(format!("{}.json", file_stem));
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Writes the table as `<stem>.md` and `<stem>.json` into the directory
+pub fn write_table(
+    dir: &Path,
+    table: &TimingTable,
+    baseline: Option<&TimingTable>,
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let json = serde_json::to_string_pretty(table)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    fs::write(dir.join(format!("{}.json", table.file_stem())), json)?;
+    fs::write(
+        dir.join(format!("{}.md", table.file_stem())),
+        table.to_markdown(baseline),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    fn get_table() -> TimingTable {
+        TimingTable {
+            year: 2022,
+            language: "rust".to_string(),
+            days: vec![DayTiming {
+                day: 1,
+                title: "calories".to_string(),
+                runs: 3,
+                min_ms: 1.0,
+                median_ms: 2.0,
+                max_ms: 3.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_timing_from_durations() {
+        let durations = [30, 10, 20, 40].map(Duration::from_millis);
+        let timing = DayTiming::from_durations(1, "calories", &durations);
+
+        assert_eq!(timing.runs, 4);
+        assert_eq!(timing.min_ms, 10.0);
+        assert_eq!(timing.median_ms, 25.0);
+        assert_eq!(timing.max_ms, 40.0);
+    }
+
+    #[test]
+    fn test_markdown_with_baseline() {
+        let table = get_table();
+        let mut baseline = get_table();
+        baseline.days[0].median_ms = 4.0;
+
+        let markdown = table.to_markdown(None);
+        assert!(markdown.contains("| 01 | calories | 3 | 1.000 | 2.000 | 3.000 |\n"));
+
+        let markdown = table.to_markdown(Some(&baseline));
+        assert!(
+            markdown.contains("| 01 | calories | 3 | 1.000 | 2.000 | 3.000 | 4.000 | -50.0% |\n")
+        );
+    }
+
+    #[test]
+    fn test_write_and_read_table() {
+        let tmp = TempDir::new("testing_bench").unwrap();
+        let table = get_table();
+
+        assert_eq!(read_table(tmp.path(), &table.file_stem()).unwrap(), None);
+
+        write_table(tmp.path(), &table, None).unwrap();
+
+        assert!(tmp.path().join("bench-2022-rust.md").exists());
+        assert_eq!(
+            read_table(tmp.path(), &table.file_stem()).unwrap(),
+            Some(table)
+        );
+    }
+}
@@ -1,9 +1,23 @@
+use crate::{
+    completions::{complete_days, complete_languages, complete_starters, complete_years},
+    config::Config,
+    index::Layout,
+    inputs::ARCHIVE_FILE,
+    registry::TEMPLATES_DIR,
+    scaffold::{DayRange, PLACEHOLDER_TITLE},
+};
 use clap::{Parser, Subcommand};
-use std::{path::PathBuf, fmt::{Display, self, Formatter}};
+use clap_complete::ArgValueCandidates;
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+};
 
+#[derive(Debug)]
 pub enum CliError {
-    BaseDirectoryError(String), 
+    BaseDirectoryError(String),
     TemplateFileError(String),
+    ConfigError(String),
 }
 
 impl Display for CliError {
@@ -11,16 +25,18 @@ impl Display for CliError {
         match self {
             CliError::BaseDirectoryError(e) => write!(f, "Base directory error: {}", e),
             CliError::TemplateFileError(e) => write!(f, "Template file error: {}", e),
+            CliError::ConfigError(e) => write!(f, "Config error: {}", e),
         }
     }
 }
 
+impl std::error::Error for CliError {}
 
 #[derive(Parser)]
 #[command(
     version = "0.1.0",
     author,
-    about, 
+    about,
     long_about = None
 )]
 pub struct Cli {
@@ -28,47 +44,193 @@ pub struct Cli {
     #[arg(short, long)]
     pub base_directory: Option<PathBuf>,
 
-    /// The template file with the languages
+    /// The template file with the languages. Can be given more than once;
+    /// later files override the languages of the previous ones and of `templates/`
     #[arg(short, long)]
-    pub template_file: Option<PathBuf>,
+    pub template_file: Vec<PathBuf>,
 
     /// Sub command to execute
     #[command(subcommand)]
     pub command: Commands,
-}
 
+    /// Defaults loaded from the config files and environment variables
+    #[arg(skip)]
+    pub config: Config,
+
+    /// Where each day lives inside the base directory
+    #[arg(skip)]
+    pub layout: Layout,
+}
 
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Creates the directory of a day with the template of its language
     Add {
-        /// The day to add
+        /// The day to add. Defaults to the first day that does not exist yet
+        #[arg(add = ArgValueCandidates::new(complete_days))]
+        day: Option<u8>,
+
+        /// The year to add. Defaults to the configured year or the current advent
+        #[arg(add = ArgValueCandidates::new(complete_years))]
+        year: Option<u16>,
+
+        /// The language to add. Defaults to the configured language, the last
+        /// one used or the only one in the template
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+
+        /// Title of the challenge. Prompted for if not given
         #[arg()]
+        title: Option<String>,
+
+        /// Prompts for every missing argument and asks for confirmation.
+        /// Used by default when the day is missing
+        #[arg(short, long)]
+        interactive: bool,
+
+        /// Opens the new day in the editor
+        #[arg(short, long)]
+        open: bool,
+    },
+    /// Opens a day in the editor: the `editor` of the config, `$VISUAL` or `$EDITOR`
+    Open {
+        /// The year of the day
+        #[arg(add = ArgValueCandidates::new(complete_years))]
+        year: u16,
+
+        /// The day to open
+        #[arg(add = ArgValueCandidates::new(complete_days))]
         day: u8,
 
-        /// The year to add
-        #[arg()]
+        /// The language of the day. Needed when the day exists in several languages
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+    },
+    /// Reruns the tests of a day, or its solution, every time its files change
+    Watch {
+        /// The year of the day
+        #[arg(add = ArgValueCandidates::new(complete_years))]
         year: u16,
 
-        /// The language to add
-        #[arg()]
+        /// The day to watch
+        #[arg(add = ArgValueCandidates::new(complete_days))]
+        day: u8,
+
+        /// The language of the day. Needed when the day exists in several languages
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+
+        /// Reruns the `run` command of the template instead of `test`
+        #[arg(short, long)]
+        run: bool,
+
+        /// Milliseconds without changes before the command reruns
+        #[arg(short, long, default_value_t = 300)]
+        debounce: u64,
+    },
+    /// Creates a day in another language with the inputs, notes and answers of an existing one
+    Port {
+        /// The year of the day
+        #[arg(add = ArgValueCandidates::new(complete_years))]
+        year: u16,
+
+        /// The day to port
+        #[arg(add = ArgValueCandidates::new(complete_days))]
+        day: u8,
+
+        /// Language of the existing day
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
+        from: String,
+
+        /// Language of the new day
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
+        to: String,
+    },
+    /// Changes the title of an existing day, moving its directory
+    Rename {
+        /// The year of the day
+        #[arg(add = ArgValueCandidates::new(complete_years))]
+        year: u16,
+
+        /// The language of the day
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
         language: String,
 
-        /// Title of the challenge
+        /// The day to rename
+        #[arg(add = ArgValueCandidates::new(complete_days))]
+        day: u8,
+
+        /// New title of the day
         #[arg()]
         title: String,
+
+        /// Also updates the template files that embedded the old title,
+        /// showing the changes first
+        #[arg(short, long)]
+        render: bool,
+
+        /// Applies the changes of `--render` without asking
+        #[arg(short, long)]
+        yes: bool,
     },
-    Remove {
+    /// Records the answers of a day, running its solution for the missing ones,
+    /// and commits them when `git_commit` is configured
+    Solve {
+        /// The year of the day
+        #[arg(add = ArgValueCandidates::new(complete_years))]
+        year: u16,
+
+        /// The language of the day
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
+        language: String,
+
+        /// The day to solve
+        #[arg(add = ArgValueCandidates::new(complete_days))]
+        day: u8,
 
+        /// Answer of the first part
+        #[arg(long)]
+        part1: Option<String>,
+
+        /// Answer of the second part
+        #[arg(long)]
+        part2: Option<String>,
+    },
+    /// Creates every day of a year, or a range of days, that does not exist yet
+    ScaffoldYear {
+        /// The year to scaffold. Defaults to the configured year or the current advent
+        #[arg(add = ArgValueCandidates::new(complete_years))]
+        year: Option<u16>,
+
+        /// The language to scaffold. Defaults to the configured language or
+        /// the only one in the template
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+
+        /// Days to create, like `1..=25`, `1-5` or `1,3,7`
+        #[arg(short, long, default_value = "1..=25")]
+        days: DayRange,
+
+        /// Title of the days, until their puzzle is known
+        #[arg(short, long, default_value = PLACEHOLDER_TITLE)]
+        title: String,
+
+        /// Number of days created at the same time
+        #[arg(short, long, default_value_t = 4)]
+        jobs: usize,
+    },
+    /// Removes the directory of a year or a day
+    Remove {
         /// The year to remove
-        #[arg()]
+        #[arg(add = ArgValueCandidates::new(complete_years))]
         year: Option<u16>,
 
         /// The day to remove
-        #[arg()]
+        #[arg(add = ArgValueCandidates::new(complete_days))]
         day: Option<u8>,
 
         /// The language to remove
-        #[arg()]
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
         language: Option<String>,
 
         /// Force it without prompting
@@ -78,50 +240,243 @@ pub enum Commands {
     /// Lists the challenges on a particular year
     List {
         /// The year to list. If none is provided, it will list all the years
-        #[arg()]
+        #[arg(add = ArgValueCandidates::new(complete_years))]
         year: Option<u16>,
 
         /// Lang to list
-        #[arg()]
+        #[arg(add = ArgValueCandidates::new(complete_languages))]
         lang: Option<String>,
+    },
+    /// Runs every day and compares its output with the recorded answers
+    Verify {
+        /// Only verify the days of this year
+        #[arg(short, long, add = ArgValueCandidates::new(complete_years))]
+        year: Option<u16>,
+
+        /// Only verify the days of this language
+        #[arg(short, long, add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+    },
+    /// Times the solution of every day and writes a table per year and language
+    Bench {
+        /// Only benchmark the days of this year
+        #[arg(short, long, add = ArgValueCandidates::new(complete_years))]
+        year: Option<u16>,
+
+        /// Only benchmark the days of this language
+        #[arg(short, long, add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+
+        /// Number of times each day is run
+        #[arg(short = 'n', long, default_value_t = 10)]
+        runs: usize,
+
+        /// Directory where the tables are written. Defaults to `benchmarks/` in the base directory
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Directory with previously written tables to compare against
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+    },
+    /// Shows how long after the unlock of the puzzle (midnight EST) each day
+    /// was created and each part was solved
+    Times {
+        /// Only show the days of this year
+        #[arg(short, long, add = ArgValueCandidates::new(complete_years))]
+        year: Option<u16>,
 
+        /// Only show the days of this language
+        #[arg(short, long, add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+    },
+    /// Inspects the template files
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+    /// Keeps the puzzle inputs outside the repository
+    Input {
+        #[command(subcommand)]
+        command: InputCommands,
+    },
+    /// Bootstraps a repository with starter templates, a `.gitignore` and a config file
+    Init {
+        /// Languages of the starter templates. Prompted for in a terminal,
+        /// otherwise every starter is used
+        #[arg(add = ArgValueCandidates::new(complete_starters))]
+        languages: Vec<String>,
+
+        /// Overwrite the template and config files if they exist
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Prints the script that adds the completions of a shell, like
+    /// `source <(advent-code-cli completions bash)`
+    Completions {
+        /// The shell of the script
+        #[arg(value_parser = ["bash", "elvish", "fish", "powershell", "zsh"])]
+        shell: String,
+    },
+    /// Writes the man pages and a markdown reference of every command
+    Reference {
+        /// Directory of the reference
+        #[arg(short, long, default_value = "docs")]
+        output: PathBuf,
+    },
+    /// Diagnoses the config, the template files, the tools of each language
+    /// and the directory of the days
+    Doctor,
+    /// Writes a progress table of every year into the README
+    Readme {
+        /// README to update. Defaults to `README.md` in the base directory
+        #[arg(short, long)]
+        file: Option<PathBuf>,
     },
 }
 
+#[derive(Subcommand)]
+pub enum TemplateCommands {
+    /// Lists every language, the template file it comes from and the aliases
+    /// it shares with other languages
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum InputCommands {
+    /// Saves the input of a day into the store
+    Store {
+        /// The year of the input
+        year: u16,
+
+        /// The day of the input
+        day: u8,
+
+        /// File with the input. Read from the standard input if not given
+        file: Option<PathBuf>,
+    },
+    /// Moves the inputs of the days into the store and links them back
+    Collect {
+        /// Only collect the days of this year
+        #[arg(short, long, add = ArgValueCandidates::new(complete_years))]
+        year: Option<u16>,
+
+        /// Only collect the days of this language
+        #[arg(short, long, add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+    },
+    /// Links the stored inputs into the days that have none
+    Link {
+        /// Only link the days of this year
+        #[arg(short, long, add = ArgValueCandidates::new(complete_years))]
+        year: Option<u16>,
+
+        /// Only link the days of this language
+        #[arg(short, long, add = ArgValueCandidates::new(complete_languages))]
+        language: Option<String>,
+
+        /// Copies the inputs instead of linking them
+        #[arg(short, long)]
+        copy: bool,
+    },
+    /// Writes every stored input into an archive encrypted with a password,
+    /// which can be committed
+    Pack {
+        /// The archive to write
+        #[arg(short, long, default_value = ARCHIVE_FILE)]
+        output: PathBuf,
+    },
+    /// Stores the inputs of an encrypted archive
+    Unpack {
+        /// The archive to read
+        #[arg(default_value = ARCHIVE_FILE)]
+        archive: PathBuf,
+
+        /// Replaces the inputs already in the store
+        #[arg(short, long)]
+        force: bool,
+    },
+}
 
 impl Cli {
+    /// Loads the config with the base directory and layout it sets. Nothing
+    /// is checked to exist
+    pub fn load_config(&mut self) -> Result<(), CliError> {
+        let current_dir = std::env::current_dir().map_err(|e| {
+            CliError::BaseDirectoryError(format!("Unable to read the current directory: {}", e))
+        })?;
 
+        // `doctor` reports the problems instead of failing on them
+        let doctor = matches!(self.command, Commands::Doctor);
 
-    pub fn init(&mut self) -> Result<(), CliError> {
-        let base_directory = std::env::current_dir().unwrap();
-        // Set the default base directory to the current directory
+        self.config = match Config::load_layers(&current_dir) {
+            Ok(config) => config,
+            Err(_) if doctor => Config::default(),
+            Err(e) => return Err(CliError::ConfigError(e)),
+        };
+
+        // Set the default base directory to the configured one or the current directory
         if self.base_directory.is_none() {
-            self.base_directory = Some(base_directory.clone());
+            self.base_directory = Some(self.config.base_directory.clone().unwrap_or(current_dir));
+        }
+
+        if let Some(layout) = &self.config.layout {
+            match Layout::new(layout) {
+                Ok(layout) => self.layout = layout,
+                Err(_) if doctor => {}
+                Err(e) => return Err(CliError::ConfigError(e)),
+            }
         }
 
+        Ok(())
+    }
+
+    pub fn init(&mut self) -> Result<(), CliError> {
+        self.load_config()?;
+        let doctor = matches!(self.command, Commands::Doctor);
+
+        // `init` creates the base directory and the template file, and the
+        // generated scripts and docs do not depend on them
+        let standalone = matches!(
+            self.command,
+            Commands::Init { .. } | Commands::Completions { .. } | Commands::Reference { .. }
+        );
+
         // Check if the base directory exists
-        if !self.base_directory.as_ref().unwrap().exists() {
+        if !standalone && !doctor && !self.base_directory.as_ref().unwrap().exists() {
             return Err(CliError::BaseDirectoryError(format!(
                 "Base directory does not exist. Please select a new base directory: {:?}",
                 self.base_directory
             )));
         }
 
+        // Set the default template file to the configured one or the base directory
+        if !standalone && self.template_file.is_empty() {
+            let template_file = self
+                .config
+                .template_file
+                .clone()
+                .unwrap_or_else(|| self.base_directory.as_ref().unwrap().join("template.yml"));
+            let templates_dir = self.base_directory.as_ref().unwrap().join(TEMPLATES_DIR);
 
-        // Set the default template file to the current directory
-        if self.template_file.is_none() {
-            self.template_file = Some(base_directory.join("template.yml"));
+            // The default template file is optional when there is a templates directory
+            if template_file.exists() || !templates_dir.is_dir() {
+                self.template_file.push(template_file);
+            }
         }
 
-        // Check if the template file exists
-        if !self.template_file.as_ref().unwrap().exists() {
+        // Check if the template files exist
+        if let Some(missing) = self
+            .template_file
+            .iter()
+            .find(|t| !standalone && !doctor && !t.exists())
+        {
             return Err(CliError::TemplateFileError(format!(
                 "Template file does not exist. Create a new one to continue: {:?}",
-                self.template_file
+                missing
             )));
         }
 
         Ok(())
     }
-
 }
@@ -0,0 +1,185 @@
+use std::io;
+
+use clap::Parser;
+use clap_complete::{env::Shells, CompletionCandidate};
+
+use crate::{
+    cli::Cli,
+    file_handler::AventStructure,
+    index::{scan_days, DayEntry},
+    registry::{TemplateRegistry, TEMPLATES_DIR},
+    starters::STARTERS,
+};
+
+/// Environment variable the shell scripts set to ask the binary for completions
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Name of the binary called by the shell scripts
+pub const BIN_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// Writes the script that registers the completions of a shell
+pub fn write_registration(shell: &str, buf: &mut dyn io::Write) -> io::Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells.completer(shell).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("There are no completions for {}", shell),
+        )
+    })?;
+
+    completer.write_registration(COMPLETE_VAR, BIN_NAME, BIN_NAME, BIN_NAME, buf)
+}
+
+/// Defaults of the cli in the current directory, with the template files.
+/// The flags of the command line being completed are not known, so the
+/// config files are used
+fn current_cli() -> Option<Cli> {
+    let mut cli = Cli::try_parse_from([BIN_NAME, "template", "list"]).ok()?;
+    cli.init().ok()?;
+    Some(cli)
+}
+
+/// Days of the base directory of the config. They are found without the
+/// template files, which may not exist yet
+fn current_days() -> Vec<DayEntry> {
+    let Some(mut cli) = Cli::try_parse_from([BIN_NAME, "list"]).ok() else {
+        return vec![];
+    };
+    if cli.load_config().is_err() {
+        return vec![];
+    }
+    let structure = AventStructure::new(cli.base_directory.unwrap_or_default());
+
+    scan_days(&structure.base_directory, &cli.layout).unwrap_or_default()
+}
+
+/// Languages of the registry, followed by their aliases
+pub fn language_candidates(registry: &TemplateRegistry) -> Vec<CompletionCandidate> {
+    let languages = registry.languages().unwrap_or_default();
+
+    let names = languages.iter().map(|l| {
+        CompletionCandidate::new(&l.name).help(Some(l.source.display().to_string().into()))
+    });
+    let aliases = languages.iter().flat_map(|l| {
+        l.aliases
+            .iter()
+            .map(|a| CompletionCandidate::new(a).help(Some(format!("alias of {}", l.name).into())))
+    });
+
+    names.chain(aliases).collect()
+}
+
+/// Years with days, the most recent first
+pub fn year_candidates(days: &[DayEntry]) -> Vec<CompletionCandidate> {
+    let mut years = days.iter().map(|d| d.year).collect::<Vec<u16>>();
+    years.sort_by(|a, b| b.cmp(a));
+    years.dedup();
+
+    years
+        .into_iter()
+        .map(|y| CompletionCandidate::new(y.to_string()))
+        .collect()
+}
+
+/// Days that exist in any year or language
+pub fn day_candidates(days: &[DayEntry]) -> Vec<CompletionCandidate> {
+    let mut numbers = days.iter().map(|d| d.day).collect::<Vec<u8>>();
+    numbers.sort();
+    numbers.dedup();
+
+    numbers
+        .into_iter()
+        .map(|d| CompletionCandidate::new(d.to_string()))
+        .collect()
+}
+
+/// Completes the languages of the active template files
+pub fn complete_languages() -> Vec<CompletionCandidate> {
+    let Some(cli) = current_cli() else {
+        return vec![];
+    };
+    let templates_dir = cli.base_directory.unwrap_or_default().join(TEMPLATES_DIR);
+
+    match TemplateRegistry::load(&cli.template_file, &templates_dir) {
+        Ok(registry) => language_candidates(&registry),
+        Err(_) => vec![],
+    }
+}
+
+/// Completes the years of the existing days
+pub fn complete_years() -> Vec<CompletionCandidate> {
+    year_candidates(&current_days())
+}
+
+/// Completes the existing days
+pub fn complete_days() -> Vec<CompletionCandidate> {
+    day_candidates(&current_days())
+}
+
+/// Completes the languages of the starter templates
+pub fn complete_starters() -> Vec<CompletionCandidate> {
+    STARTERS
+        .iter()
+        .map(|s| CompletionCandidate::new(s.name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::registry::TemplateSource;
+
+    fn values(candidates: Vec<CompletionCandidate>) -> Vec<String> {
+        candidates
+            .iter()
+            .map(|c| c.get_value().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_language_candidates() {
+        let registry = TemplateRegistry::new(vec![TemplateSource {
+            path: "template.yml".into(),
+            content: "rust:\n  aliases: [rs]\npython:\n  aliases: [py]\n".into(),
+        }]);
+
+        assert_eq!(
+            values(language_candidates(&registry)),
+            vec!["rust", "python", "rs", "py"]
+        );
+    }
+
+    #[test]
+    fn test_year_and_day_candidates() {
+        let day = |year, day| DayEntry {
+            day,
+            year,
+            language: "rust".into(),
+            title: "title".into(),
+            path: "".into(),
+        };
+        let days = [day(2022, 3), day(2023, 1), day(2022, 1)];
+
+        assert_eq!(values(year_candidates(&days)), vec!["2023", "2022"]);
+        assert_eq!(values(day_candidates(&days)), vec!["1", "3"]);
+    }
+
+    #[test]
+    fn test_registration_scripts() {
+        for shell in ["bash", "zsh", "fish"] {
+            let mut script = vec![];
+            write_registration(shell, &mut script).unwrap();
+            let script = String::from_utf8(script).unwrap();
+
+            assert!(
+                script.contains(COMPLETE_VAR),
+                "{} script: {}",
+                shell,
+                script
+            );
+        }
+
+        assert!(write_registration("cmd", &mut vec![]).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// Name of the config file of a repository
+pub const REPOSITORY_CONFIG_FILE: &str = ".advent.toml";
+
+/// Prefix of the environment variables that override the config files
+pub const ENV_PREFIX: &str = "ADVENT_CLI_";
+
+/// Defaults of the cli. Each layer overrides the previous one:
+/// `~/.config/advent-code-cli/config.toml`, `.advent.toml` in the repository,
+/// `ADVENT_CLI_*` environment variables and finally the command line flags
+///
+/// ```toml
+/// base_directory = "."
+/// template_file = "template.yml"
+/// language = "rust"
+/// year = 2023
+/// layout = "{year}/{language}/Day-{day}-{title}"
+/// session_file = "~/.config/advent-code-cli/session"
+/// editor = "code"
+/// git_commit = true
+/// commit_message = "Day {{ day }} {{ year }} ({{ language }}): scaffold"
+/// solve_message = "Day {{ day }} {{ year }} ({{ language }}): solve"
+/// git_branch = "day-{{ day }}-{{ year }}"
+/// input_store = "~/.local/share/advent-code-cli/inputs"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Directory with the `src/` folder of the days
+    pub base_directory: Option<PathBuf>,
+    /// Template file with the languages
+    pub template_file: Option<PathBuf>,
+    /// Language used when none is given
+    pub language: Option<String>,
+    /// Year used when none is given
+    pub year: Option<u16>,
+    /// Where each day lives inside the base directory. See `Layout`
+    pub layout: Option<String>,
+    /// File with the session token of adventofcode.com
+    pub session_file: Option<PathBuf>,
+    /// Command used to open the days
+    pub editor: Option<String>,
+    /// Commit every new day and every recorded answer
+    pub git_commit: Option<bool>,
+    /// Message of the commit of a new day, rendered like the template file
+    pub commit_message: Option<String>,
+    /// Message of the commit of the recorded answers
+    pub solve_message: Option<String>,
+    /// Branch created for every new day, rendered like the template file
+    pub git_branch: Option<String>,
+    /// Directory where the puzzle inputs are kept, outside the repository
+    pub input_store: Option<PathBuf>,
+}
+
+impl Config {
+    /// Parses a config file. Relative paths are resolved from `dir`
+    pub fn parse(content: &str, dir: &Path) -> Result<Self, String> {
+        let mut config: Config = toml::from_str(content).map_err(|e| e.to_string())?;
+        config.resolve_paths(dir);
+        Ok(config)
+    }
+
+    /// Reads a config file. A missing file is an empty config
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        Self::parse(&content, dir).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Reads the config from the `ADVENT_CLI_*` environment variables
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| env::var(format!("{}{}", ENV_PREFIX, name)).ok();
+
+        let year = match var("YEAR") {
+            Some(y) => Some(
+                y.parse()
+                    .map_err(|_| format!("{}YEAR must be a year, got {:?}", ENV_PREFIX, y))?,
+            ),
+            None => None,
+        };
+
+        let git_commit = match var("GIT_COMMIT").as_deref() {
+            Some("1" | "true") => Some(true),
+            Some("0" | "false") => Some(false),
+            Some(v) => {
+                return Err(format!(
+                    "{}GIT_COMMIT must be true or false, got {:?}",
+                    ENV_PREFIX, v
+                ))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            base_directory: var("BASE_DIRECTORY").map(PathBuf::from),
+            template_file: var("TEMPLATE_FILE").map(PathBuf::from),
+            language: var("LANGUAGE"),
+            year,
+            layout: var("LAYOUT"),
+            session_file: var("SESSION_FILE").map(|p| expand_home(Path::new(&p))),
+            editor: var("EDITOR"),
+            git_commit,
+            commit_message: var("COMMIT_MESSAGE"),
+            solve_message: var("SOLVE_MESSAGE"),
+            git_branch: var("GIT_BRANCH"),
+            input_store: var("INPUT_STORE").map(|p| expand_home(Path::new(&p))),
+        })
+    }
+
+    /// Values of `other` take precedence over the ones of `self`
+    pub fn merge(self, other: Config) -> Config {
+        Config {
+            base_directory: other.base_directory.or(self.base_directory),
+            template_file: other.template_file.or(self.template_file),
+            language: other.language.or(self.language),
+            year: other.year.or(self.year),
+            layout: other.layout.or(self.layout),
+            session_file: other.session_file.or(self.session_file),
+            editor: other.editor.or(self.editor),
+            git_commit: other.git_commit.or(self.git_commit),
+            commit_message: other.commit_message.or(self.commit_message),
+            solve_message: other.solve_message.or(self.solve_message),
+            git_branch: other.git_branch.or(self.git_branch),
+            input_store: other.input_store.or(self.input_store),
+        }
+    }
+
+    /// Loads every layer of the config, from the user config to the
+    /// environment variables. The repository config is searched from `cwd` upwards
+    pub fn load_layers(cwd: &Path) -> Result<Self, String> {
+        let mut config = match user_config_file() {
+            Some(path) => Config::load(&path)?,
+            None => Config::default(),
+        };
+
+        if let Some(path) = find_repository_config(cwd) {
+            let mut repository = Config::load(&path)?;
+
+            // The repository is the base directory unless stated otherwise
+            if repository.base_directory.is_none() {
+                repository.base_directory = path.parent().map(Path::to_path_buf);
+            }
+
+            config = config.merge(repository);
+        }
+
+        Ok(config.merge(Config::from_env()?))
+    }
+
+    fn resolve_paths(&mut self, dir: &Path) {
+        let resolve = |p: &PathBuf| {
+            let p = expand_home(p);
+            if p.is_absolute() {
+                p
+            } else {
+                dir.join(p)
+            }
+        };
+
+        self.base_directory = self.base_directory.as_ref().map(resolve);
+        self.template_file = self.template_file.as_ref().map(resolve);
+        self.session_file = self.session_file.as_ref().map(resolve);
+        self.input_store = self.input_store.as_ref().map(resolve);
+    }
+}
+
+/// Replaces a leading `~` with the home directory
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Directory with the files of the cli for the user: `~/.config/advent-code-cli`
+pub fn user_config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+        .map(|dir| dir.join("advent-code-cli"))
+}
+
+/// `~/.config/advent-code-cli/config.toml`
+pub fn user_config_file() -> Option<PathBuf> {
+    user_config_dir().map(|dir| dir.join("config.toml"))
+}
+
+/// Looks for `.advent.toml` in `start` and its parents
+pub fn find_repository_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(REPOSITORY_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_parse_config() {
+        let content = r#"
+template_file = "templates/template.yml"
+language = "rust"
+year = 2022
+layout = "{language}/{year}/{day}"
+git_commit = true
+"#;
+        let config = Config::parse(content, Path::new("/repo")).unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                template_file: Some(PathBuf::from("/repo/templates/template.yml")),
+                language: Some("rust".into()),
+                year: Some(2022),
+                layout: Some("{language}/{year}/{day}".into()),
+                git_commit: Some(true),
+                ..Default::default()
+            }
+        );
+
+        assert!(Config::parse("unknown = 1", Path::new("/repo")).is_err());
+    }
+
+    #[test]
+    fn test_merge_configs() {
+        let user = Config {
+            language: Some("python".into()),
+            year: Some(2021),
+            editor: Some("vim".into()),
+            ..Default::default()
+        };
+        let repository = Config {
+            language: Some("rust".into()),
+            ..Default::default()
+        };
+
+        let config = user.merge(repository);
+
+        assert_eq!(config.language, Some("rust".into()));
+        assert_eq!(config.year, Some(2021));
+        assert_eq!(config.editor, Some("vim".into()));
+    }
+
+    #[test]
+    fn test_find_repository_config() {
+        let tmp = TempDir::new("testing_config").unwrap();
+        let nested = tmp.path().join("src/2022/rust");
+        fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_repository_config(&nested), None);
+
+        fs::write(
+            tmp.path().join(REPOSITORY_CONFIG_FILE),
+            "language = \"rust\"",
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_repository_config(&nested),
+            Some(tmp.path().join(REPOSITORY_CONFIG_FILE))
+        );
+    }
+}
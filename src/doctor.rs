@@ -0,0 +1,408 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{find_repository_config, user_config_file, Config},
+    index::{stray_directories, DayEntry, Layout},
+    inputs::INPUT_FILE,
+    interfaces::{DayChallenge, ProgrammingTemplate},
+    registry::TemplateRegistry,
+    requires::{check_requirements, Requirement, RequirementError},
+    utils::find_executable,
+};
+
+/// Commands of the shell that are not programs on the `PATH`
+const SHELL_BUILTINS: &[&str] = &[
+    ".", ":", "[", "alias", "cd", "echo", "eval", "exec", "exit", "export", "false", "printf",
+    "read", "set", "source", "test", "true", "unset",
+];
+
+/// How bad the result of a check is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Result of a check, with how to fix it when something is wrong
+#[derive(Debug, PartialEq, Eq)]
+pub struct Check {
+    pub severity: Severity,
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+impl Check {
+    pub fn ok(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Ok,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn error(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            Severity::Ok => "ok",
+            Severity::Warning => "warn",
+            Severity::Error => "error",
+        };
+        write!(f, "[{:^5}] {}", label, self.message)?;
+
+        if let Some(fix) = &self.fix {
+            write!(f, "\n        fix: {}", fix)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Program run by a shell command, skipping the variable assignments.
+/// `None` for the builtins of the shell
+pub fn command_tool(command: &str) -> Option<String> {
+    let tool = command
+        .split_whitespace()
+        .find(|word| !word.contains('=') || word.starts_with('='))?;
+
+    (!SHELL_BUILTINS.contains(&tool)).then(|| tool.to_string())
+}
+
+/// Programs run by the commands of a language that are not in its `requires` list
+pub fn command_tools(template: &ProgrammingTemplate) -> Vec<String> {
+    let commands = template
+        .init_commands
+        .iter()
+        .chain(&template.commands)
+        .chain(&template.run)
+        .chain(&template.test)
+        .filter_map(|c| command_tool(&c.command));
+
+    let mut tools: Vec<String> = vec![];
+    for tool in commands {
+        if !tools.contains(&tool) && !template.requires.iter().any(|r| r.tool == tool) {
+            tools.push(tool);
+        }
+    }
+
+    tools
+}
+
+/// Checks every layer of the config and its layout
+pub fn check_config(cwd: &Path) -> Vec<Check> {
+    let mut checks = vec![];
+
+    let files = user_config_file()
+        .into_iter()
+        .chain(find_repository_config(cwd))
+        .filter(|path| path.exists());
+    for path in files {
+        checks.push(match Config::load(&path) {
+            Ok(_) => Check::ok(format!("{} is valid", path.display())),
+            Err(e) => Check::error(
+                format!("Config error: {}", e),
+                format!("Fix or remove the invalid keys of {}", path.display()),
+            ),
+        });
+    }
+
+    if let Err(e) = Config::from_env() {
+        checks.push(Check::error(
+            format!("Config error: {}", e),
+            "Fix or unset the environment variable",
+        ));
+    }
+
+    if let Ok(Some(layout)) = Config::load_layers(cwd).map(|c| c.layout) {
+        if let Err(e) = Layout::new(&layout) {
+            checks.push(Check::error(
+                format!("Config error: {}", e),
+                "Use a layout like \"{year}/{language}/Day-{day}-{title}\"",
+            ));
+        }
+    }
+
+    checks
+}
+
+/// Checks that every template file exists and every language renders, and
+/// that the tools of each language are on the `PATH`
+pub fn check_templates(template_files: &[PathBuf], templates_dir: &Path, year: u16) -> Vec<Check> {
+    let mut checks = vec![];
+
+    for file in template_files.iter().filter(|f| !f.exists()) {
+        checks.push(Check::error(
+            format!("The template file {} does not exist", file.display()),
+            "Create one with `advent-code-cli init` or pass --template-file",
+        ));
+    }
+    if !checks.is_empty() {
+        return checks;
+    }
+
+    let languages = match TemplateRegistry::load(template_files, templates_dir) {
+        Ok(registry) => registry.languages().map(|languages| (registry, languages)),
+        Err(e) => {
+            checks.push(Check::error(
+                e.to_string(),
+                "Check that the template files can be read",
+            ));
+            return checks;
+        }
+    };
+
+    let (registry, languages) = match languages {
+        Ok(languages) => languages,
+        Err(e) => {
+            checks.push(Check::error(
+                e.to_string(),
+                "Add a language to the template file, like the starters of `advent-code-cli init`",
+            ));
+            return checks;
+        }
+    };
+
+    for language in languages {
+        let challenge = DayChallenge::new(1, year, language.name.clone(), "doctor".into());
+
+        let template = match registry.template_for_challenge(&challenge) {
+            Ok(template) => template,
+            Err(e) => {
+                let cause = std::error::Error::source(&e)
+                    .map(|c| format!(": {}", c))
+                    .unwrap_or_default();
+                checks.push(Check::error(
+                    format!("{}: {}{}", language.name, e, cause),
+                    format!(
+                        "Fix the {} section of {}",
+                        language.name,
+                        language.source.display()
+                    ),
+                ));
+                continue;
+            }
+        };
+
+        let mut tools = template
+            .requires
+            .iter()
+            .map(Requirement::to_string)
+            .collect::<Vec<String>>();
+        let mut missing = check_requirements(&template.requires)
+            .iter()
+            .map(RequirementError::to_string)
+            .collect::<Vec<String>>();
+
+        for tool in command_tools(&template) {
+            if find_executable(&tool).is_none() {
+                missing.push(RequirementError::NotFound(tool.clone()).to_string());
+            }
+            tools.push(tool);
+        }
+
+        if tools.is_empty() {
+            checks.push(Check::ok(format!("{}: template renders", language.name)));
+        } else if missing.is_empty() {
+            checks.push(Check::ok(format!(
+                "{}: template renders and {} on the PATH",
+                language.name,
+                tools.join(", ")
+            )));
+        } else {
+            checks.push(Check::error(
+                format!("{}: {}", language.name, missing.join(", ")),
+                "Install or update the tools, or add them to the PATH",
+            ));
+        }
+    }
+
+    checks
+}
+
+/// Checks that the directory of the days exists and only has days
+pub fn check_structure(days_directory: &Path, layout: &Layout) -> Vec<Check> {
+    if !days_directory.is_dir() {
+        return vec![Check::error(
+            format!("{} does not exist", days_directory.display()),
+            "Run `advent-code-cli init` in the base directory or pass --base-directory",
+        )];
+    }
+
+    let stray = match stray_directories(days_directory, layout) {
+        Ok(stray) => stray,
+        Err(e) => {
+            return vec![Check::error(
+                format!("Unable to read {}: {}", days_directory.display(), e),
+                "Check the permissions of the directory",
+            )]
+        }
+    };
+
+    let mut checks = stray
+        .into_iter()
+        .map(|path| {
+            Check::warning(
+                format!(
+                    "{} does not match the layout {}",
+                    path.display(),
+                    layout.pattern()
+                ),
+                "Rename it to match the layout, move it out of the directory of the days or \
+                 set `layout` in the config",
+            )
+        })
+        .collect::<Vec<Check>>();
+
+    if checks.is_empty() {
+        checks.push(Check::ok(format!(
+            "{} matches the layout {}",
+            days_directory.display(),
+            layout.pattern()
+        )));
+    }
+
+    checks
+}
+
+/// Checks that every day has an input
+pub fn check_inputs(days: &[DayEntry]) -> Vec<Check> {
+    let mut checks = days
+        .iter()
+        .filter(|day| fs::metadata(day.path.join(INPUT_FILE)).map_or(true, |m| m.len() == 0))
+        .map(|day| {
+            Check::warning(
+                format!(
+                    "{} {} day {:02} has an empty or missing {}",
+                    day.year, day.language, day.day, INPUT_FILE
+                ),
+                format!(
+                    "Save it with `advent-code-cli input store {} {} <file>` and \
+                     `advent-code-cli input link`",
+                    day.year, day.day
+                ),
+            )
+        })
+        .collect::<Vec<Check>>();
+
+    if days.is_empty() {
+        checks.push(Check::ok("There are no days yet"));
+    } else if checks.is_empty() {
+        checks.push(Check::ok(format!("The {} days have an input", days.len())));
+    }
+
+    checks
+}
+
+/// Checks that the session token of adventofcode.com can be read
+pub fn check_session(config: &Config) -> Check {
+    let fix = "Save the `session` cookie of adventofcode.com in a file and set \
+               `session_file` in the config";
+
+    match &config.session_file {
+        None => Check::warning("There is no session token", fix),
+        Some(path) => match fs::read_to_string(path) {
+            Ok(token) if !token.trim().is_empty() => {
+                Check::ok(format!("Session token found in {}", path.display()))
+            }
+            Ok(_) => Check::warning(format!("The session file {} is empty", path.display()), fix),
+            Err(e) => Check::warning(
+                format!("Unable to read the session file {}: {}", path.display(), e),
+                fix,
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    use crate::interfaces::Command;
+
+    #[test]
+    fn test_command_tools() {
+        assert_eq!(command_tool("cargo build -q"), Some("cargo".into()));
+        assert_eq!(
+            command_tool("DEMO_APP=1 python3 main.py"),
+            Some("python3".into())
+        );
+        assert_eq!(command_tool("echo hi > notes.md"), None);
+
+        let template = ProgrammingTemplate {
+            requires: vec!["cargo>=1.70".parse().unwrap()],
+            init_commands: vec![Command::new("cargo init -q", vec![])],
+            commands: vec![Command::new("cd src", vec![])],
+            run: Some(Command::new("rustc main.rs", vec![])),
+            ..Default::default()
+        };
+
+        assert_eq!(command_tools(&template), vec!["rustc"]);
+    }
+
+    #[test]
+    fn test_check_templates() {
+        let tmp = TempDir::new("testing_doctor").unwrap();
+        let template_file = tmp.path().join("template.yml");
+        fs::write(
+            &template_file,
+            "shell:\n  commands:\n    - sh -c true\nmissing:\n  requires: [advent-code-cli-missing-tool]\n",
+        )
+        .unwrap();
+
+        let checks = check_templates(&[template_file], &tmp.path().join("templates"), 2023);
+
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].severity, Severity::Ok);
+        assert_eq!(checks[1].severity, Severity::Error);
+        assert!(checks[1].message.contains("advent-code-cli-missing-tool"));
+    }
+
+    #[test]
+    fn test_check_inputs_and_session() {
+        let tmp = TempDir::new("testing_doctor").unwrap();
+        let day = |n: u8| DayEntry {
+            day: n,
+            year: 2023,
+            language: "rust".into(),
+            title: "title".into(),
+            path: tmp.path().join(n.to_string()),
+        };
+        fs::create_dir_all(tmp.path().join("1")).unwrap();
+        fs::write(tmp.path().join("1").join(INPUT_FILE), "1abc2").unwrap();
+
+        let checks = check_inputs(&[day(1), day(2)]);
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].message.contains("day 02"));
+
+        let config = Config {
+            session_file: Some(tmp.path().join("session")),
+            ..Default::default()
+        };
+        assert_eq!(check_session(&config).severity, Severity::Warning);
+        fs::write(tmp.path().join("session"), "53616c7465645f5f").unwrap();
+        assert_eq!(check_session(&config).severity, Severity::Ok);
+    }
+}
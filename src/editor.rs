@@ -0,0 +1,80 @@
+use std::{
+    env, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Command that opens the days: the `editor` of the config, `$VISUAL` or `$EDITOR`
+pub fn find_editor(configured: Option<&str>) -> Option<String> {
+    let from_env = |name| env::var(name).ok();
+
+    configured
+        .map(String::from)
+        .or_else(|| from_env("VISUAL"))
+        .or_else(|| from_env("EDITOR"))
+        .filter(|editor| !editor.trim().is_empty())
+}
+
+/// Paths given to the editor: the directory of the day and its entry file,
+/// when the template declares one that exists
+pub fn editor_paths(day_path: &Path, entry: Option<&str>) -> Vec<PathBuf> {
+    let mut paths = vec![day_path.to_path_buf()];
+    paths.extend(
+        entry
+            .map(|entry| day_path.join(entry))
+            .filter(|entry| entry.is_file()),
+    );
+    paths
+}
+
+/// Opens the paths with the editor from the directory of the day. The editor
+/// may have arguments, like `code --wait`, and keeps the terminal until it exits
+pub fn open_day(editor: &str, day_path: &Path, entry: Option<&str>) -> io::Result<()> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(editor)
+        .args(editor_paths(day_path, entry))
+        .current_dir(day_path)
+        .status()?;
+
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "`{}` exited with {}",
+            editor, status
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_open_day_with_its_entry() {
+        let tmp = TempDir::new("testing_editor").unwrap();
+        let day = tmp.path().join("Day-1-Trebuchet");
+        fs::create_dir_all(day.join("src")).unwrap();
+        fs::write(day.join("src/main.rs"), "fn main() {}").unwrap();
+
+        assert_eq!(find_editor(Some("vim -p")), Some("vim -p".into()));
+        assert_eq!(
+            editor_paths(&day, Some("main.py")),
+            vec![day.clone()],
+            "Missing entries are not opened"
+        );
+
+        open_day("printf '%s\\n' > opened.txt", &day, Some("src/main.rs")).unwrap();
+        assert_eq!(
+            fs::read_to_string(day.join("opened.txt")).unwrap(),
+            format!("{}\n{}\n", day.display(), day.join("src/main.rs").display())
+        );
+        assert!(open_day("false", &day, None).is_err());
+    }
+}
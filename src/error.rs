@@ -0,0 +1,239 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+use crate::{
+    answers::AnswersError, cli::CliError, file_handler::BuildError, git::GitError,
+    inputs::InputsError, times::TimesError, yaml_parser::YamlParserError,
+};
+
+/// Exit code when some solutions no longer give their recorded answers
+pub const EXIT_REGRESSION: i32 = 1;
+/// Exit code when the arguments are not valid
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code for problems with the base directory, template file or config
+pub const EXIT_CONFIG: i32 = 3;
+/// Exit code for problems reading or rendering the template file
+pub const EXIT_TEMPLATE: i32 = 4;
+/// Exit code for problems building a day
+pub const EXIT_BUILD: i32 = 5;
+/// Exit code for problems reading or writing files
+pub const EXIT_IO: i32 = 6;
+/// Exit code when `doctor` finds problems
+pub const EXIT_UNHEALTHY: i32 = 7;
+
+/// Every error of the cli
+#[derive(Debug)]
+pub enum Error {
+    /// The arguments are not valid
+    Usage(String),
+    /// Problems with the base directory, template file or config
+    Cli(CliError),
+    /// Problems reading or rendering the template file
+    Template(YamlParserError),
+    /// Problems building a day
+    Build(BuildError),
+    /// Problems reading the recorded answers of a day
+    Answers(AnswersError),
+    /// Problems committing a day
+    Git(GitError),
+    /// Problems with the input store
+    Inputs(InputsError),
+    /// Problems reading or writing the times of a day
+    Times(TimesError),
+    /// Problems reading or writing files
+    Io { context: String, source: io::Error },
+    /// Number of days that no longer give their recorded answers
+    Regressions(usize),
+    /// Number of days that could not be created
+    FailedDays(usize),
+    /// Number of problems found by `doctor`
+    Unhealthy(usize),
+}
+
+impl Error {
+    /// Wraps an io error with what was being done
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Error::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Exit code of the process for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Regressions(_) => EXIT_REGRESSION,
+            Error::Usage(_) => EXIT_USAGE,
+            Error::Cli(_) => EXIT_CONFIG,
+            Error::Template(_) => EXIT_TEMPLATE,
+            Error::Build(_) | Error::FailedDays(_) => EXIT_BUILD,
+            Error::Answers(_)
+            | Error::Git(_)
+            | Error::Inputs(_)
+            | Error::Times(_)
+            | Error::Io { .. } => EXIT_IO,
+            Error::Unhealthy(_) => EXIT_UNHEALTHY,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(m) => write!(f, "{}", m),
+            Error::Cli(e) => write!(f, "{}", e),
+            Error::Template(e) => write!(f, "{}", e),
+            Error::Build(e) => write!(f, "{}", e),
+            Error::Answers(e) => write!(f, "{}", e),
+            Error::Git(e) => write!(f, "{}", e),
+            Error::Inputs(e) => write!(f, "{}", e),
+            Error::Times(e) => write!(f, "{}", e),
+            Error::Io { context, .. } => write!(f, "{}", context),
+            Error::Regressions(n) => write!(f, "{} days no longer give their recorded answers", n),
+            Error::FailedDays(n) => write!(f, "{} days could not be created", n),
+            Error::Unhealthy(n) => write!(f, "{} problems found", n),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        // Wrapped errors are transparent, their message is already displayed
+        match self {
+            Error::Cli(e) => e.source(),
+            Error::Template(e) => e.source(),
+            Error::Build(e) => e.source(),
+            Error::Answers(e) => e.source(),
+            Error::Git(e) => e.source(),
+            Error::Inputs(e) => e.source(),
+            Error::Times(e) => e.source(),
+            Error::Io { source, .. } => Some(source),
+            Error::Usage(_)
+            | Error::Regressions(_)
+            | Error::FailedDays(_)
+            | Error::Unhealthy(_) => None,
+        }
+    }
+}
+
+impl From<CliError> for Error {
+    fn from(e: CliError) -> Self {
+        Error::Cli(e)
+    }
+}
+
+impl From<YamlParserError> for Error {
+    fn from(e: YamlParserError) -> Self {
+        Error::Template(e)
+    }
+}
+
+impl From<BuildError> for Error {
+    fn from(e: BuildError) -> Self {
+        Error::Build(e)
+    }
+}
+
+impl From<AnswersError> for Error {
+    fn from(e: AnswersError) -> Self {
+        Error::Answers(e)
+    }
+}
+
+impl From<GitError> for Error {
+    fn from(e: GitError) -> Self {
+        Error::Git(e)
+    }
+}
+
+impl From<InputsError> for Error {
+    fn from(e: InputsError) -> Self {
+        Error::Inputs(e)
+    }
+}
+
+impl From<TimesError> for Error {
+    fn from(e: TimesError) -> Self {
+        Error::Times(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, error::Error as _, mem};
+
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            Error::Usage("usage".into()),
+            Error::Cli(CliError::ConfigError("config".into())),
+            Error::Template(YamlParserError::NoLanguagesProvided),
+            Error::Build(BuildError::CommandError {
+                command: "cargo".into(),
+                source: io::Error::other("build"),
+            }),
+            Error::Answers(AnswersError::BadFormat("answers".into())),
+            Error::Git(GitError::NotARepository(".".into())),
+            Error::Inputs(InputsError::BadArchive("inputs".into())),
+            Error::Times(TimesError::IoError(io::Error::other("times"))),
+            Error::io("io", io::Error::other("io")),
+            Error::Regressions(1),
+            Error::FailedDays(1),
+            Error::Unhealthy(1),
+        ];
+
+        // Lists every variant, so a new one must be given a category here
+        let category = |error: &Error| match error {
+            Error::Usage(_) => "usage",
+            Error::Cli(_) => "config",
+            Error::Template(_) => "template",
+            Error::Build(_) | Error::FailedDays(_) => "build",
+            Error::Answers(_)
+            | Error::Git(_)
+            | Error::Inputs(_)
+            | Error::Times(_)
+            | Error::Io { .. } => "io",
+            Error::Regressions(_) => "regressions",
+            Error::Unhealthy(_) => "unhealthy",
+        };
+
+        let variants = errors.iter().map(mem::discriminant).collect::<HashSet<_>>();
+        assert_eq!(variants.len(), errors.len(), "Each variant is listed once");
+
+        for a in &errors {
+            assert_ne!(a.exit_code(), 0);
+            for b in &errors {
+                assert_eq!(
+                    category(a) == category(b),
+                    a.exit_code() == b.exit_code(),
+                    "{:?} and {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_source_chain_is_preserved() {
+        let error = Error::io(
+            "Unable to read the template file",
+            io::Error::new(io::ErrorKind::NotFound, "missing"),
+        );
+
+        assert_eq!(error.to_string(), "Unable to read the template file");
+        assert_eq!(error.source().unwrap().to_string(), "missing");
+
+        let yaml_error = serde_yaml::from_str::<serde_yaml::Value>("a: [").unwrap_err();
+        let error = Error::from(YamlParserError::YamlError(yaml_error));
+
+        assert!(
+            error.source().is_some(),
+            "Expected the yaml error as source"
+        );
+    }
+}
@@ -1,98 +1,284 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+};
 
-use crate::interfaces::{DayChallenge, ProgrammingTemplate};
+use crate::{
+    index::Layout,
+    interfaces::{Command, DayChallenge, ProgrammingTemplate},
+    requires::{check_requirements, RequirementError},
+    utils::run_shell_command_with_env,
+    workspace::{register_day, unregister_day, WorkspaceError},
+};
 
+/// Error type for the creation of a day
 #[derive(Debug)]
 pub enum BuildError {
-    FileError,
-    TemplateError(String),
-    DirectoryError,
+    /// A file of the template could not be written
+    FileError {
+        path: PathBuf,
+        operation: &'static str,
+        source: io::Error,
+    },
+    /// A command of the template could not be started
+    CommandError { command: String, source: io::Error },
+    /// A command of the template exited with an error
+    TemplateError {
+        command: String,
+        exit_code: Option<i32>,
+        stdout: String,
+        stderr: String,
+    },
+    /// A directory could not be created
+    DirectoryError {
+        path: PathBuf,
+        operation: &'static str,
+        source: io::Error,
+    },
+    /// Tools of the `requires` list are missing or too old
+    RequirementsError {
+        language: String,
+        errors: Vec<RequirementError>,
+    },
+    /// The day could not be added to or removed from a workspace manifest
+    WorkspaceError(WorkspaceError),
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::FileError {
+                path, operation, ..
+            } => write!(f, "Unable to {} the file {}", operation, path.display()),
+            BuildError::CommandError { command, .. } => {
+                write!(f, "Unable to execute `{}`", command)
+            }
+            BuildError::TemplateError {
+                command,
+                exit_code,
+                stdout,
+                stderr,
+            } => {
+                match exit_code {
+                    Some(code) => write!(f, "`{}` failed with exit code {}", command, code)?,
+                    None => write!(f, "`{}` was terminated by a signal", command)?,
+                }
+
+                for (name, output) in [("stdout", stdout), ("stderr", stderr)] {
+                    if !output.trim().is_empty() {
+                        write!(f, "\n\n{}:\n{}", name, output.trim_end())?;
+                    }
+                }
+
+                Ok(())
+            }
+            BuildError::DirectoryError {
+                path, operation, ..
+            } => write!(
+                f,
+                "Unable to {} the directory {}",
+                operation,
+                path.display()
+            ),
+            BuildError::RequirementsError { language, errors } => {
+                write!(f, "The tools required by {} are not ready:", language)?;
+                for error in errors {
+                    write!(f, "\n  - {}", error)?;
+                }
+                Ok(())
+            }
+            BuildError::WorkspaceError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::FileError { source, .. }
+            | BuildError::CommandError { source, .. }
+            | BuildError::DirectoryError { source, .. } => Some(source),
+            BuildError::TemplateError { .. } | BuildError::RequirementsError { .. } => None,
+            BuildError::WorkspaceError(e) => e.source(),
+        }
+    }
+}
+
+impl From<WorkspaceError> for BuildError {
+    fn from(e: WorkspaceError) -> Self {
+        BuildError::WorkspaceError(e)
+    }
+}
+
+/// Environment variables with the metadata of the day, given to the hooks
+pub fn hook_env(challenge: &DayChallenge, day_path: &Path) -> Vec<(&'static str, String)> {
+    vec![
+        ("AOC_DAY", challenge.day.to_string()),
+        ("AOC_YEAR", challenge.year.to_string()),
+        ("AOC_LANGUAGE", challenge.language.clone()),
+        ("AOC_TITLE", challenge.title.clone()),
+        ("AOC_DIR", day_path.display().to_string()),
+    ]
+}
+
+/// Runs a command of the template inside a directory
+fn run_template_command(
+    command: &str,
+    dir: &Path,
+    env: &[(&str, String)],
+) -> Result<(), BuildError> {
+    let output =
+        run_shell_command_with_env(command, dir, env).map_err(|e| BuildError::CommandError {
+            command: command.to_string(),
+            source: e,
+        })?;
+
+    if !output.status.success() {
+        return Err(BuildError::TemplateError {
+            command: command.to_string(),
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+fn create_directory(path: &Path) -> Result<(), BuildError> {
+    fs::create_dir_all(path).map_err(|e| BuildError::DirectoryError {
+        path: path.to_path_buf(),
+        operation: "create",
+        source: e,
+    })
 }
 
 /// Hanldes the creation of the directory structure for the advent of code
 pub struct AventStructure {
     pub base_directory: PathBuf,
+    /// Where each day lives inside the base directory
+    pub layout: Layout,
 }
 
 impl AventStructure {
     pub fn new(base_directory: PathBuf) -> AventStructure {
         AventStructure {
             base_directory: base_directory.join("src"),
+            layout: Layout::default(),
         }
     }
 
+    /// Directory where the hooks run, the parent of `src/`
+    pub fn root_directory(&self) -> &Path {
+        self.base_directory
+            .parent()
+            .unwrap_or(self.base_directory.as_path())
+    }
+
+    fn run_hooks(
+        &self,
+        hooks: &[Command],
+        challenge: &DayChallenge,
+        day_path: &Path,
+    ) -> Result<(), BuildError> {
+        let env = hook_env(challenge, day_path);
+        for hook in hooks {
+            run_template_command(&hook.command, self.root_directory(), &env)?;
+        }
+
+        Ok(())
+    }
+
     /// Creates the directory of a day challenge structure for the advent of code
     pub fn add_day(
-        &mut self,
+        &self,
         challenge: &DayChallenge,
         template: &ProgrammingTemplate,
     ) -> Result<PathBuf, BuildError> {
-        let year_path = self
-            .base_directory
-            .join(challenge.year.to_string())
-            .join(challenge.language.to_string())
-            .join(format!("Day-{:02}-{}", challenge.day, challenge.title));
-        fs::create_dir_all(&year_path).map_err(|_| BuildError::DirectoryError)?;
+        // Nothing is created when a tool of the commands is missing
+        let errors = check_requirements(&template.requires);
+        if !errors.is_empty() {
+            return Err(BuildError::RequirementsError {
+                language: challenge.language.clone(),
+                errors,
+            });
+        }
+
+        let year_path = self.base_directory.join(self.layout.day_path(challenge));
+        self.run_hooks(&template.hooks.pre_add, challenge, &year_path)?;
+        create_directory(&year_path)?;
 
         // Run the init commands
         for c in &template.init_commands {
-            let ouput = std::process::Command::new("sh")
-                .arg("-c")
-                .arg(&c.command)
-                .current_dir(&year_path)
-                .output()
-                .map_err(|_| {
-                    BuildError::TemplateError(format!("{} failed to execute", c.command))
-                })?;
-
-            if !ouput.status.success() {
-                return Err(BuildError::TemplateError(format!(
-                    "{} failed to execute",
-                    c.command
-                )));
-            }
+            run_template_command(&c.command, &year_path, &[])?;
         }
 
         // Create the folders
         for folder in &template.folders {
-            let folder_path = year_path.join(folder);
-            fs::create_dir_all(&folder_path).map_err(|_| BuildError::DirectoryError)?;
+            create_directory(&year_path.join(folder))?;
         }
 
         // Create the files
         for f in &template.files {
             let file_path = year_path.join(&f.name);
-            fs::write(&file_path, &f.content.clone().unwrap_or("".to_string()))
-                .map_err(|_| BuildError::FileError)?;
+            fs::write(&file_path, f.content.clone().unwrap_or("".to_string())).map_err(|e| {
+                BuildError::FileError {
+                    path: file_path.clone(),
+                    operation: "write",
+                    source: e,
+                }
+            })?;
         }
 
+        // Days with a manifest of their own join the workspace of the root
+        // directory before its commands build them
+        register_day(self.root_directory(), &year_path)?;
+
         // Run the commands
         for c in &template.commands {
-            let ouput = std::process::Command::new("sh")
-                .arg("-c")
-                .arg(&c.command)
-                .current_dir(&year_path)
-                .output()
-                .map_err(|e| {
-                    BuildError::TemplateError(format!("{} failed to execute\n\n{:?}", c.command, e))
-                })?;
-
-            if !ouput.status.success() {
-                return Err(BuildError::TemplateError(format!(
-                    "{} failed to execute\n\n{}",
-                    c.command,
-                    String::from_utf8_lossy(&ouput.stderr)
-                )));
-            }
+            run_template_command(&c.command, &year_path, &[])?;
         }
 
+        self.run_hooks(&template.hooks.post_add, challenge, &year_path)?;
+
         Ok(year_path)
     }
+
+    /// Removes the directory of a day, and the directories of the layout
+    /// that are left empty
+    pub fn remove_day(
+        &self,
+        challenge: &DayChallenge,
+        day_path: &Path,
+        template: &ProgrammingTemplate,
+    ) -> Result<(), BuildError> {
+        self.run_hooks(&template.hooks.pre_remove, challenge, day_path)?;
+        unregister_day(self.root_directory(), day_path)?;
+
+        fs::remove_dir_all(day_path).map_err(|e| BuildError::DirectoryError {
+            path: day_path.to_path_buf(),
+            operation: "remove",
+            source: e,
+        })?;
+
+        for parent in day_path.ancestors().skip(1) {
+            if parent == self.base_directory || !parent.starts_with(&self.base_directory) {
+                break;
+            }
+            // Stops at the first directory that is not empty
+            if fs::remove_dir(parent).is_err() {
+                break;
+            }
+        }
+
+        self.run_hooks(&template.hooks.post_remove, challenge, day_path)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::interfaces::{Command, File};
+    use crate::interfaces::{File, Hooks};
 
     use super::*;
 
@@ -121,17 +307,132 @@ mod tests {
                 File::new("demo-input.txt", "demo"),
             ],
             folders: vec!["docs/".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_failing_command_keeps_its_output() {
+        let tmp = get_tmp_dir();
+        let base_dir = tmp.path().join("src");
+        let structure = AventStructure::new(base_dir);
+        let template = ProgrammingTemplate {
+            language: "rust".to_string(),
+            init_commands: vec![Command::new(
+                "echo building; echo broken >&2; exit 3",
+                vec![],
+            )],
+            ..Default::default()
+        };
+
+        let err = structure.add_day(&get_challenge(), &template).unwrap_err();
+
+        match &err {
+            BuildError::TemplateError {
+                exit_code,
+                stdout,
+                stderr,
+                ..
+            } => {
+                assert_eq!(*exit_code, Some(3));
+                assert_eq!(stdout, "building\n");
+                assert_eq!(stderr, "broken\n");
+            }
+            e => panic!("Expected a template error. Got {:?}", e),
+        }
+
+        assert!(err.to_string().contains("failed with exit code 3"));
+    }
+
+    #[test]
+    fn test_missing_requirements_create_nothing() {
+        let tmp = get_tmp_dir();
+        let structure = AventStructure::new(tmp.path().to_path_buf());
+        let template = ProgrammingTemplate {
+            requires: vec!["advent-code-cli-missing-tool>=1.0".parse().unwrap()],
+            ..get_template()
+        };
+
+        let err = structure.add_day(&get_challenge(), &template).unwrap_err();
+
+        assert!(matches!(err, BuildError::RequirementsError { .. }));
+        assert!(err
+            .to_string()
+            .contains("advent-code-cli-missing-tool is not on the PATH"));
+        assert!(!structure.base_directory.exists());
+    }
+
+    #[test]
+    fn test_hooks_run_around_add_and_remove() {
+        let tmp = get_tmp_dir();
+        let structure = AventStructure::new(tmp.path().to_path_buf());
+        let log = |hook: &str| {
+            Command::new(
+                &format!(
+                    "echo \"{} $AOC_YEAR $AOC_DAY $AOC_LANGUAGE $AOC_TITLE $(test -d \"$AOC_DIR\" && echo exists)\" >> hooks.log",
+                    hook
+                ),
+                vec![],
+            )
+        };
+        let template = ProgrammingTemplate {
+            hooks: Hooks {
+                pre_add: vec![log("pre_add")],
+                post_add: vec![log("post_add")],
+                pre_remove: vec![log("pre_remove")],
+                post_remove: vec![log("post_remove")],
+            },
+            ..Default::default()
+        };
+
+        let path = structure.add_day(&get_challenge(), &template).unwrap();
+        structure
+            .remove_day(&get_challenge(), &path, &template)
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("hooks.log")).unwrap(),
+            "pre_add 2015 1 rust test-my-challenge \n\
+             post_add 2015 1 rust test-my-challenge exists\n\
+             pre_remove 2015 1 rust test-my-challenge exists\n\
+             post_remove 2015 1 rust test-my-challenge \n"
+        );
+        // The empty directories of the year and language are removed too
+        assert!(structure.base_directory.exists());
+        assert!(!structure.base_directory.join("2015").exists());
+    }
+
+    #[test]
+    fn test_file_error_keeps_the_path() {
+        let tmp = get_tmp_dir();
+        let base_dir = tmp.path().join("src");
+        let structure = AventStructure::new(base_dir);
+        let template = ProgrammingTemplate {
+            language: "rust".to_string(),
+            files: vec![File::empty("missing-folder/input.txt")],
+            ..Default::default()
+        };
+
+        let err = structure.add_day(&get_challenge(), &template).unwrap_err();
+
+        match err {
+            BuildError::FileError { path, source, .. } => {
+                assert!(path.ends_with("missing-folder/input.txt"));
+                assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+            }
+            e => panic!("Expected a file error. Got {:?}", e),
         }
     }
 
     #[test]
     fn test_initialize_year_folder_correctly() {
-        let base_dir = get_tmp_dir().as_ref().join("src");
+        let tmp = get_tmp_dir();
+        let base_dir = tmp.path().join("src");
         let challenge = get_challenge();
         let template = get_template();
         const YEAR: u32 = 2015;
         let base_dir_c = base_dir.clone();
-        let mut structure = AventStructure::new(base_dir);
+        let structure = AventStructure::new(base_dir);
         let base_dir = base_dir_c;
 
         let new_dir = structure.add_day(&challenge, &template).unwrap();
@@ -140,7 +441,7 @@ mod tests {
         let challenge_path = base_dir
             .join("src")
             .join(YEAR.to_string())
-            .join(challenge.language.to_string())
+            .join(&challenge.language)
             .join(format!("Day-{:02}-{}", challenge.day, challenge.title));
 
         println!("{:?}", challenge_path);
@@ -0,0 +1,224 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    interfaces::DayChallenge,
+    yaml_parser::{populate_yml, YamlParserError},
+};
+
+/// Message of the commit made after a day is created
+pub const DEFAULT_COMMIT_MESSAGE: &str = "Day {{ day }} {{ year }} ({{ language }}): scaffold";
+
+/// Message of the commit made after the answers of a day are recorded
+pub const DEFAULT_SOLVE_MESSAGE: &str = "Day {{ day }} {{ year }} ({{ language }}): solve";
+
+/// Files that are never committed. Advent of code asks not to publish the inputs
+pub const EXCLUDED_FILES: &[&str] = &["input.txt"];
+
+/// Error type for the git commands
+#[derive(Debug)]
+pub enum GitError {
+    /// git could not be executed
+    CommandError { command: String, source: io::Error },
+    /// git exited with an error
+    CommandFailed { command: String, stderr: String },
+    /// The directory is not inside a git repository
+    NotARepository(PathBuf),
+}
+
+impl Display for GitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GitError::CommandError { command, .. } => write!(f, "Unable to run `{}`", command),
+            GitError::CommandFailed { command, stderr } => {
+                write!(f, "`{}` failed: {}", command, stderr.trim())
+            }
+            GitError::NotARepository(path) => {
+                write!(f, "{} is not inside a git repository", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for GitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GitError::CommandError { source, .. } => Some(source),
+            GitError::CommandFailed { .. } | GitError::NotARepository(_) => None,
+        }
+    }
+}
+
+/// Runs git inside `dir` and returns its standard output
+fn git(dir: &Path, args: &[&str]) -> Result<String, GitError> {
+    let command = format!("git {}", args.join(" "));
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| GitError::CommandError {
+            command: command.clone(),
+            source: e,
+        })?;
+
+    if !output.status.success() {
+        return Err(GitError::CommandFailed {
+            command,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Pathspecs of the current directory without the excluded files
+fn day_pathspecs() -> Vec<String> {
+    let mut pathspecs = vec![".".to_string()];
+    pathspecs.extend(
+        EXCLUDED_FILES
+            .iter()
+            .map(|file| format!(":(exclude,glob)**/{}", file)),
+    );
+    pathspecs
+}
+
+/// Root of the repository that contains `dir`
+pub fn repository_root(dir: &Path) -> Result<PathBuf, GitError> {
+    git(dir, &["rev-parse", "--show-toplevel"])
+        .map(|root| PathBuf::from(root.trim()))
+        .map_err(|_| GitError::NotARepository(dir.to_path_buf()))
+}
+
+/// Renders a commit message or branch name with the metadata of the day
+pub fn render_message(template: &str, challenge: &DayChallenge) -> Result<String, YamlParserError> {
+    populate_yml(template, challenge).map(|m| m.trim().to_string())
+}
+
+/// Switches to the branch, creating it when it does not exist
+pub fn switch_branch(repo: &Path, branch: &str) -> Result<(), GitError> {
+    let reference = format!("refs/heads/{}", branch);
+    let exists = git(repo, &["rev-parse", "--verify", "--quiet", &reference]).is_ok();
+
+    if exists {
+        git(repo, &["switch", branch])?;
+    } else {
+        git(repo, &["switch", "-c", branch])?;
+    }
+
+    Ok(())
+}
+
+/// Commits the changes of the day directory and of the other files, like the
+/// workspace manifests that list the day, without the excluded files and
+/// leaving anything else that was staged untouched. Returns false when there
+/// was nothing to commit
+pub fn commit_day(day_path: &Path, files: &[PathBuf], message: &str) -> Result<bool, GitError> {
+    let mut pathspecs = day_pathspecs();
+    if !files.is_empty() {
+        let root = repository_root(day_path)?;
+        for file in files {
+            let file = file.canonicalize().unwrap_or(file.to_path_buf());
+            if let Some(path) = file.strip_prefix(&root).ok().and_then(|p| p.to_str()) {
+                pathspecs.push(format!(":(top,literal){}", path.replace('\\', "/")));
+            }
+        }
+    }
+    let pathspecs = pathspecs.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    let mut add = vec!["add", "-A", "--"];
+    add.extend(&pathspecs);
+    git(day_path, &add)?;
+
+    let mut status = vec!["status", "--porcelain", "--"];
+    status.extend(&pathspecs);
+    if git(day_path, &status)?.trim().is_empty() {
+        return Ok(false);
+    }
+
+    let mut commit = vec!["commit", "-q", "-m", message, "--"];
+    commit.extend(&pathspecs);
+    git(day_path, &commit)?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use tempdir::TempDir;
+
+    fn repository() -> TempDir {
+        let tmp = TempDir::new("testing_git").unwrap();
+        git(tmp.path(), &["init", "-q"]).unwrap();
+        git(tmp.path(), &["config", "user.name", "advent"]).unwrap();
+        git(tmp.path(), &["config", "user.email", "advent@localhost"]).unwrap();
+        tmp
+    }
+
+    #[test]
+    fn test_render_message() {
+        let challenge = DayChallenge::new(3, 2023, "rust".into(), "Gear Ratios".into());
+
+        assert_eq!(
+            render_message(DEFAULT_COMMIT_MESSAGE, &challenge).unwrap(),
+            "Day 3 2023 (rust): scaffold"
+        );
+    }
+
+    #[test]
+    fn test_commit_day_excludes_inputs() {
+        let tmp = repository();
+        let repo = tmp.path();
+        let day = repo.join("src/2023/rust/Day-1-Trebuchet");
+        fs::create_dir_all(&day).unwrap();
+        fs::write(day.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(day.join("input.txt"), "1abc2").unwrap();
+        fs::write(repo.join("staged.txt"), "not part of the day").unwrap();
+        git(repo, &["add", "staged.txt"]).unwrap();
+        fs::write(repo.join("Cargo.toml"), "[workspace]").unwrap();
+
+        assert!(commit_day(&day, &[repo.join("Cargo.toml")], "Day 1").unwrap());
+
+        let committed = git(repo, &["show", "--name-only", "--format=%s", "HEAD"]).unwrap();
+        assert_eq!(
+            committed
+                .lines()
+                .filter(|l| !l.is_empty())
+                .collect::<Vec<_>>(),
+            vec![
+                "Day 1",
+                "Cargo.toml",
+                "src/2023/rust/Day-1-Trebuchet/main.rs"
+            ]
+        );
+
+        // Nothing changed since the last commit
+        assert!(!commit_day(&day, &[], "Day 1").unwrap());
+    }
+
+    #[test]
+    fn test_switch_branch() {
+        let tmp = repository();
+        let repo = tmp.path();
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "initial"]).unwrap();
+
+        switch_branch(repo, "day-1").unwrap();
+        switch_branch(repo, "day-1").unwrap();
+
+        assert_eq!(
+            git(repo, &["branch", "--show-current"]).unwrap().trim(),
+            "day-1"
+        );
+        assert!(matches!(
+            repository_root(&std::env::temp_dir().join("no-repository")),
+            Err(GitError::NotARepository(_))
+        ));
+    }
+}
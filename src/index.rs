@@ -0,0 +1,420 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use crate::interfaces::DayChallenge;
+
+/// A day found in the directory structure created by `AventStructure::add_day`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayEntry {
+    /// Number of the day
+    pub day: u8,
+    /// Year of the day
+    pub year: u16,
+    /// Language used
+    pub language: String,
+    /// Title of the challenge
+    pub title: String,
+    /// Directory of the day
+    pub path: PathBuf,
+}
+
+impl DayEntry {
+    /// Metadata of the challenge stored in this directory
+    pub fn challenge(&self) -> DayChallenge {
+        DayChallenge {
+            day: self.day,
+            year: self.year,
+            language: self.language.clone(),
+            title: self.title.clone(),
+        }
+    }
+}
+
+/// Default layout of the days inside the base directory
+pub const DEFAULT_LAYOUT: &str = "{year}/{language}/Day-{day}-{title}";
+
+/// Where the directory of a day lives inside the base directory.
+/// A pattern of `/` separated segments with the placeholders `{year}`,
+/// `{language}`, `{day}` (padded to two digits) and `{title}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pattern: String,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            pattern: DEFAULT_LAYOUT.to_string(),
+        }
+    }
+}
+
+impl Layout {
+    /// Creates a layout, checking that a day can be found with the pattern
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let pattern = pattern.trim().trim_matches('/').to_string();
+
+        for placeholder in ["{year}", "{language}", "{day}"] {
+            if !pattern.contains(placeholder) {
+                return Err(format!(
+                    "The layout {:?} must contain {}",
+                    pattern, placeholder
+                ));
+            }
+        }
+
+        Ok(Self { pattern })
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Number of directories between the base directory and a day
+    pub fn depth(&self) -> usize {
+        self.pattern.split('/').count()
+    }
+
+    /// Path of the directory of a challenge, relative to the base directory
+    pub fn day_path(&self, challenge: &DayChallenge) -> PathBuf {
+        PathBuf::from(
+            self.pattern
+                .replace("{year}", &challenge.year.to_string())
+                .replace("{language}", &challenge.language)
+                .replace("{day}", &format!("{:02}", challenge.day))
+                .replace("{title}", &challenge.title),
+        )
+    }
+
+    fn regex(&self) -> Regex {
+        let mut expression = regex::escape(&self.pattern);
+
+        for (placeholder, group) in [
+            ("{year}", r"(?P<year>\d+)"),
+            ("{language}", r"(?P<language>[^/]+?)"),
+            ("{day}", r"(?P<day>\d+)"),
+            ("{title}", r"(?P<title>[^/]*)"),
+        ] {
+            expression = expression.replacen(&regex::escape(placeholder), group, 1);
+        }
+
+        Regex::new(&format!("^{}$", expression)).expect("The layout is always a valid regex")
+    }
+
+    /// Parses the path of a day relative to the base directory
+    pub fn parse(&self, relative_path: &str) -> Option<DayChallenge> {
+        let captures = self.regex().captures(relative_path)?;
+
+        Some(DayChallenge {
+            day: captures.name("day")?.as_str().parse().ok()?,
+            year: captures.name("year")?.as_str().parse().ok()?,
+            language: captures.name("language")?.as_str().to_string(),
+            title: captures
+                .name("title")
+                .map(|t| t.as_str().to_string())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Lists the subdirectories of a directory, ignoring the ones that cannot be read
+fn sub_directories(path: &Path) -> io::Result<Vec<PathBuf>> {
+    Ok(fs::read_dir(path)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect())
+}
+
+/// Directories of the base directory as deep as the days of the layout
+fn layout_directories(base_directory: &Path, layout: &Layout) -> io::Result<Vec<PathBuf>> {
+    let mut level = vec![base_directory.to_path_buf()];
+    for _ in 0..layout.depth() {
+        level = level
+            .iter()
+            .map(|dir| sub_directories(dir))
+            .collect::<io::Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+    }
+
+    Ok(level)
+}
+
+/// Path of a directory relative to the base directory, with `/` separators
+fn relative_path(base_directory: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(base_directory)
+        .ok()
+        .and_then(|p| p.to_str())
+        .map(|p| p.replace('\\', "/"))
+}
+
+/// Directories as deep as the days that do not match the layout
+pub fn stray_directories(base_directory: &Path, layout: &Layout) -> io::Result<Vec<PathBuf>> {
+    if !base_directory.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut stray = layout_directories(base_directory, layout)?
+        .into_iter()
+        .filter(|path| {
+            relative_path(base_directory, path).is_none_or(|p| layout.parse(&p).is_none())
+        })
+        .collect::<Vec<PathBuf>>();
+    stray.sort();
+
+    Ok(stray)
+}
+
+/// Finds every day in the base directory of an `AventStructure`,
+/// sorted by year, language and day
+pub fn scan_days(base_directory: &Path, layout: &Layout) -> io::Result<Vec<DayEntry>> {
+    let mut days = vec![];
+
+    if !base_directory.exists() {
+        return Ok(days);
+    }
+
+    let level = layout_directories(base_directory, layout)?;
+
+    for path in level {
+        let Some(relative) = relative_path(base_directory, &path) else {
+            continue;
+        };
+
+        if let Some(challenge) = layout.parse(&relative) {
+            days.push(DayEntry {
+                day: challenge.day,
+                year: challenge.year,
+                language: challenge.language,
+                title: challenge.title,
+                path,
+            });
+        }
+    }
+
+    days.sort_by(|a, b| {
+        (a.year, &a.language, a.day, &a.title).cmp(&(b.year, &b.language, b.day, &b.title))
+    });
+
+    Ok(days)
+}
+
+/// Keeps the days matching the year and language, if given
+pub fn filter_days(
+    days: Vec<DayEntry>,
+    year: Option<u16>,
+    language: Option<&str>,
+) -> Vec<DayEntry> {
+    days.into_iter()
+        .filter(|d| year.is_none_or(|y| d.year == y))
+        .filter(|d| language.is_none_or(|l| d.language.eq_ignore_ascii_case(l)))
+        .collect()
+}
+
+/// Years of the days, without duplicates
+pub fn years(days: &[DayEntry]) -> Vec<u16> {
+    let mut years = days.iter().map(|d| d.year).collect::<Vec<u16>>();
+    years.sort();
+    years.dedup();
+    years
+}
+
+/// Languages of the days, without duplicates
+pub fn languages(days: &[DayEntry]) -> Vec<String> {
+    let mut languages = days
+        .iter()
+        .map(|d| d.language.clone())
+        .collect::<Vec<String>>();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// Language of the day directory that was modified last
+pub fn most_recent_language(days: &[DayEntry]) -> Option<String> {
+    days.iter()
+        .filter_map(|d| Some((fs::metadata(&d.path).ok()?.modified().ok()?, d)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, d)| d.language.clone())
+}
+
+/// First day of the advent without a directory for the year and language,
+/// or `None` when every day exists
+pub fn next_missing_day(days: &[DayEntry], year: u16, language: &str) -> Option<u8> {
+    (1..=25).find(|n| find_day(days, year, language, *n).is_none())
+}
+
+/// Day of the year and language, if it exists
+pub fn find_day<'a>(
+    days: &'a [DayEntry],
+    year: u16,
+    language: &str,
+    day: u8,
+) -> Option<&'a DayEntry> {
+    days.iter()
+        .find(|d| d.year == year && d.language.eq_ignore_ascii_case(language) && d.day == day)
+}
+
+/// Day that `add` creates: the given one, which must not exist yet, or the
+/// first missing one
+pub fn day_to_add(
+    days: &[DayEntry],
+    year: u16,
+    language: &str,
+    day: Option<u8>,
+) -> Result<u8, String> {
+    match day {
+        Some(day) => match find_day(days, year, language, day) {
+            Some(existing) => Err(format!(
+                "Day {} of {} already exists in {}: {}",
+                day,
+                year,
+                language,
+                existing.path.display()
+            )),
+            None => Ok(day),
+        },
+        None => next_missing_day(days, year, language)
+            .ok_or_else(|| format!("Every day of {} in {} exists", year, language)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_default_layout() {
+        let layout = Layout::default();
+        let challenge = DayChallenge::new(5, 2022, "rust".into(), "supply-stacks".into());
+
+        assert_eq!(
+            layout.day_path(&challenge),
+            PathBuf::from("2022/rust/Day-05-supply-stacks")
+        );
+
+        let parsed = layout.parse("2022/rust/Day-05-supply-stacks").unwrap();
+        assert_eq!(
+            (parsed.day, parsed.year, parsed.language, parsed.title),
+            (5, 2022, "rust".to_string(), "supply-stacks".to_string())
+        );
+
+        assert!(layout.parse("2022/rust/Day-xx-title").is_none());
+        assert!(layout.parse("2022/rust/docs").is_none());
+    }
+
+    #[test]
+    fn test_custom_layout() {
+        let layout = Layout::new("/{language}/{year}-{day}/").unwrap();
+        let challenge = DayChallenge::new(1, 2015, "go".into(), "floors".into());
+
+        assert_eq!(layout.depth(), 2);
+        assert_eq!(layout.day_path(&challenge), PathBuf::from("go/2015-01"));
+
+        let parsed = layout.parse("go/2015-01").unwrap();
+        assert_eq!(
+            (parsed.day, parsed.year, parsed.title),
+            (1, 2015, "".into())
+        );
+
+        assert!(Layout::new("{year}/{language}").is_err());
+    }
+
+    #[test]
+    fn test_list_days_of_a_custom_layout() {
+        let tmp = TempDir::new("testing_index").unwrap();
+        let base = tmp.path();
+        let layout = Layout::new("{language}/{year}/{day}").unwrap();
+
+        for dir in ["rust/2023/01", "rust/2023/02", "go/2023/01", "go/2022/05"] {
+            fs::create_dir_all(base.join(dir)).unwrap();
+        }
+
+        let days = scan_days(base, &layout).unwrap();
+        assert_eq!(years(&days), vec![2022, 2023]);
+
+        let days = filter_days(days, Some(2023), None);
+        assert_eq!(languages(&days), vec!["go", "rust"]);
+
+        let days = filter_days(days, Some(2023), Some("rust"));
+        assert_eq!(days.iter().map(|d| d.day).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(days[1].path, base.join("rust/2023/02"));
+    }
+
+    #[test]
+    fn test_day_to_add_never_reuses_a_day() {
+        let day = |n| DayEntry {
+            day: n,
+            year: 2023,
+            language: "rust".into(),
+            title: "title".into(),
+            path: PathBuf::from(format!("2023/rust/Day-{:02}-title", n)),
+        };
+        let mut days = (1..=24).map(day).collect::<Vec<DayEntry>>();
+
+        assert_eq!(day_to_add(&days, 2023, "rust", None), Ok(25));
+        assert_eq!(day_to_add(&days, 2023, "python", Some(3)), Ok(3));
+        assert_eq!(
+            day_to_add(&days, 2023, "Rust", Some(3)),
+            Err("Day 3 of 2023 already exists in Rust: 2023/rust/Day-03-title".into())
+        );
+
+        days.push(day(25));
+        assert_eq!(next_missing_day(&days, 2023, "rust"), None);
+        assert_eq!(
+            day_to_add(&days, 2023, "rust", None),
+            Err("Every day of 2023 in rust exists".into())
+        );
+    }
+
+    #[test]
+    fn test_scan_days() {
+        let tmp = TempDir::new("testing_index").unwrap();
+        let base = tmp.path();
+
+        for dir in [
+            "2022/rust/Day-02-rock-paper",
+            "2022/rust/Day-01-calories",
+            "2022/python/Day-01-calories",
+            "2022/rust/target",
+            "notes/rust/Day-01-calories",
+        ] {
+            fs::create_dir_all(base.join(dir)).unwrap();
+        }
+
+        let days = scan_days(base, &Layout::default()).unwrap();
+        let found = days
+            .iter()
+            .map(|d| (d.year, d.language.as_str(), d.day))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            found,
+            vec![(2022, "python", 1), (2022, "rust", 1), (2022, "rust", 2)]
+        );
+
+        assert_eq!(next_missing_day(&days, 2022, "rust"), Some(3));
+        assert_eq!(next_missing_day(&days, 2022, "python"), Some(2));
+        assert_eq!(next_missing_day(&days, 2023, "rust"), Some(1));
+
+        let rust_days = filter_days(days, Some(2022), Some("rust"));
+        assert_eq!(rust_days.len(), 2);
+        assert_eq!(rust_days[0].path, base.join("2022/rust/Day-01-calories"));
+
+        assert_eq!(
+            stray_directories(base, &Layout::default()).unwrap(),
+            vec![
+                base.join("2022/rust/target"),
+                base.join("notes/rust/Day-01-calories")
+            ]
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::{self, Display, Formatter},
+    fs,
+    io::{self, Read, Write},
+    iter,
+    path::{Path, PathBuf},
+};
+
+use age::secrecy::SecretString;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// File of the puzzle input inside the directory of a day
+pub const INPUT_FILE: &str = "input.txt";
+
+/// Default file of the encrypted archive of the inputs
+pub const ARCHIVE_FILE: &str = "inputs.age";
+
+/// Environment variable with the password of the archive
+pub const PASSWORD_VAR: &str = "ADVENT_CLI_INPUT_PASSWORD";
+
+/// Error type for the input store
+#[derive(Debug)]
+pub enum InputsError {
+    IoError(io::Error),
+    DecryptError(age::DecryptError),
+    BadArchive(String),
+}
+
+impl Display for InputsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputsError::IoError(_) => write!(f, "Unable to access the input store"),
+            InputsError::DecryptError(_) => {
+                write!(f, "Unable to decrypt the inputs. Check the password")
+            }
+            InputsError::BadArchive(m) => write!(f, "Bad archive! {}", m),
+        }
+    }
+}
+
+impl std::error::Error for InputsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputsError::IoError(e) => Some(e),
+            InputsError::DecryptError(e) => Some(e),
+            InputsError::BadArchive(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for InputsError {
+    fn from(e: io::Error) -> Self {
+        InputsError::IoError(e)
+    }
+}
+
+/// How a stored input gets into the directory of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceMode {
+    /// Symbolic link to the stored file
+    Link,
+    /// Copy of the stored file
+    Copy,
+}
+
+/// What happened to the input of a day
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputOutcome {
+    /// The day links to the stored input
+    Linked(PathBuf),
+    /// The stored input was copied into the day
+    Copied(PathBuf),
+    /// The input of the day was moved into the store
+    Collected(PathBuf),
+    /// The store has no input for the day
+    Missing,
+    /// The input of the day was left as it is
+    Kept(String),
+}
+
+impl Display for InputOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputOutcome::Linked(path) => write!(f, "linked to {}", path.display()),
+            InputOutcome::Copied(path) => write!(f, "copied from {}", path.display()),
+            InputOutcome::Collected(path) => write!(f, "moved to {}", path.display()),
+            InputOutcome::Missing => write!(f, "not in the store"),
+            InputOutcome::Kept(reason) => write!(f, "kept, {}", reason),
+        }
+    }
+}
+
+/// Directory of the inputs outside the repository:
+/// `~/.local/share/advent-code-cli/inputs`
+pub fn default_store_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+        .map(|dir| dir.join("advent-code-cli").join("inputs"))
+}
+
+/// Creates a symbolic link to a file
+#[cfg(unix)]
+pub fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+/// Creates a symbolic link to a file
+#[cfg(windows)]
+pub fn symlink(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(original, link)
+}
+
+/// Whether the day has an input of its own, and not an empty file or a link
+fn has_own_input(input: &Path) -> io::Result<bool> {
+    match fs::symlink_metadata(input) {
+        Ok(metadata) => Ok(metadata.is_file() && metadata.len() > 0),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Removes the input of a day, whether it is a file or a link
+fn remove_input(input: &Path) -> io::Result<()> {
+    match fs::remove_file(input) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Inputs kept outside the repository, one per year and day:
+/// `<root>/<year>/<day>/input.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputStore {
+    pub root: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Stored input of a day
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(day.to_string())
+            .join(INPUT_FILE)
+    }
+
+    /// Whether the store has the input of a day
+    pub fn contains(&self, year: u16, day: u8) -> bool {
+        self.path(year, day).is_file()
+    }
+
+    /// Stores the input of a day, replacing the previous one
+    pub fn save(&self, year: u16, day: u8, content: &[u8]) -> io::Result<PathBuf> {
+        let path = self.path(year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    /// Years and days of every stored input
+    pub fn days(&self) -> io::Result<Vec<(u16, u8)>> {
+        let mut days = vec![];
+        if !self.root.is_dir() {
+            return Ok(days);
+        }
+
+        for year in fs::read_dir(&self.root)? {
+            let year = year?;
+            let Some(year_number) = year.file_name().to_str().and_then(|y| y.parse().ok()) else {
+                continue;
+            };
+
+            for day in fs::read_dir(year.path())? {
+                let day = day?;
+                let Some(day_number) = day.file_name().to_str().and_then(|d| d.parse().ok()) else {
+                    continue;
+                };
+
+                if day.path().join(INPUT_FILE).is_file() {
+                    days.push((year_number, day_number));
+                }
+            }
+        }
+
+        days.sort();
+        Ok(days)
+    }
+
+    /// Puts the stored input of a day into its directory. An input of the day
+    /// itself is never replaced
+    pub fn place(
+        &self,
+        day_path: &Path,
+        year: u16,
+        day: u8,
+        mode: PlaceMode,
+    ) -> io::Result<InputOutcome> {
+        let stored = self.path(year, day);
+        if !stored.is_file() {
+            return Ok(InputOutcome::Missing);
+        }
+
+        let input = day_path.join(INPUT_FILE);
+        if has_own_input(&input)? {
+            return Ok(InputOutcome::Kept("the day has its own input".into()));
+        }
+        remove_input(&input)?;
+
+        match mode {
+            PlaceMode::Link => {
+                symlink(&stored, &input)?;
+                Ok(InputOutcome::Linked(stored))
+            }
+            PlaceMode::Copy => {
+                fs::copy(&stored, &input)?;
+                Ok(InputOutcome::Copied(stored))
+            }
+        }
+    }
+
+    /// Moves the input of a day into the store and links it back
+    pub fn collect(&self, day_path: &Path, year: u16, day: u8) -> io::Result<InputOutcome> {
+        let input = day_path.join(INPUT_FILE);
+        if !has_own_input(&input)? {
+            return Ok(InputOutcome::Kept("the day has no input of its own".into()));
+        }
+
+        let content = fs::read(&input)?;
+        let stored = self.path(year, day);
+        if stored.is_file() && fs::read(&stored)? != content {
+            return Ok(InputOutcome::Kept(format!(
+                "it differs from {}",
+                stored.display()
+            )));
+        }
+
+        self.save(year, day, &content)?;
+        fs::remove_file(&input)?;
+        symlink(&stored, &input)?;
+
+        Ok(InputOutcome::Collected(stored))
+    }
+
+    /// Writes every stored input into an archive encrypted with the password.
+    /// The inputs are kept as base64, so any file round-trips.
+    /// Returns the number of inputs
+    pub fn pack(&self, archive: &Path, password: &str) -> Result<usize, InputsError> {
+        let mut inputs = BTreeMap::new();
+        for (year, day) in self.days()? {
+            let content = fs::read(self.path(year, day))?;
+            inputs.insert(format!("{}/{}", year, day), STANDARD.encode(content));
+        }
+
+        let content = serde_json::to_vec(&inputs)
+            .map_err(|e| InputsError::BadArchive(format!("Unable to serialize: {}", e)))?;
+
+        let encryptor =
+            age::Encryptor::with_user_passphrase(SecretString::from(password.to_string()));
+        let mut encrypted = vec![];
+        let mut writer = encryptor.wrap_output(&mut encrypted)?;
+        writer.write_all(&content)?;
+        writer.finish()?;
+
+        fs::write(archive, encrypted)?;
+        Ok(inputs.len())
+    }
+
+    /// Stores the inputs of an encrypted archive. Stored inputs are only
+    /// replaced with `force`. Returns the number of stored inputs
+    pub fn unpack(
+        &self,
+        archive: &Path,
+        password: &str,
+        force: bool,
+    ) -> Result<usize, InputsError> {
+        let encrypted = fs::read(archive)?;
+        let decryptor = age::Decryptor::new_buffered(encrypted.as_slice())
+            .map_err(InputsError::DecryptError)?;
+
+        let identity = age::scrypt::Identity::new(SecretString::from(password.to_string()));
+        let mut reader = decryptor
+            .decrypt(iter::once(&identity as &dyn age::Identity))
+            .map_err(InputsError::DecryptError)?;
+
+        let mut content = vec![];
+        reader.read_to_end(&mut content)?;
+
+        let inputs: BTreeMap<String, String> =
+            serde_json::from_slice(&content).map_err(|e| InputsError::BadArchive(e.to_string()))?;
+
+        let mut stored = 0;
+        for (key, content) in inputs {
+            let (year, day) = key
+                .split_once('/')
+                .and_then(|(y, d)| Some((y.parse().ok()?, d.parse().ok()?)))
+                .ok_or_else(|| InputsError::BadArchive(format!("{} is not a year/day", key)))?;
+
+            let content = STANDARD.decode(content).map_err(|e| {
+                InputsError::BadArchive(format!("The input of {} is not base64: {}", key, e))
+            })?;
+
+            if force || !self.contains(year, day) {
+                self.save(year, day, &content)?;
+                stored += 1;
+            }
+        }
+
+        Ok(stored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_collect_and_place_inputs() {
+        let tmp = TempDir::new("testing_inputs").unwrap();
+        let store = InputStore::new(tmp.path().join("store"));
+        let day = tmp.path().join("Day-01-Trebuchet");
+        fs::create_dir_all(&day).unwrap();
+
+        assert_eq!(
+            store.place(&day, 2023, 1, PlaceMode::Link).unwrap(),
+            InputOutcome::Missing
+        );
+
+        fs::write(day.join(INPUT_FILE), "1abc2").unwrap();
+        assert_eq!(
+            store.collect(&day, 2023, 1).unwrap(),
+            InputOutcome::Collected(store.path(2023, 1))
+        );
+        assert!(fs::symlink_metadata(day.join(INPUT_FILE))
+            .unwrap()
+            .is_symlink());
+        assert_eq!(store.days().unwrap(), vec![(2023, 1)]);
+
+        // Links are replaced by a copy, but not the other way around
+        assert_eq!(
+            store.place(&day, 2023, 1, PlaceMode::Copy).unwrap(),
+            InputOutcome::Copied(store.path(2023, 1))
+        );
+        assert!(matches!(
+            store.place(&day, 2023, 1, PlaceMode::Link).unwrap(),
+            InputOutcome::Kept(_)
+        ));
+        assert_eq!(fs::read_to_string(day.join(INPUT_FILE)).unwrap(), "1abc2");
+    }
+
+    #[test]
+    fn test_pack_and_unpack_inputs() {
+        let tmp = TempDir::new("testing_inputs").unwrap();
+        let store = InputStore::new(tmp.path().join("store"));
+        store.save(2022, 1, b"1000\n2000").unwrap();
+        store.save(2023, 25, b"1=-0-2").unwrap();
+        // Inputs are bytes, not always valid UTF-8
+        store.save(2024, 3, b"mul(\xff,\xfe)").unwrap();
+
+        let archive = tmp.path().join(ARCHIVE_FILE);
+        assert_eq!(store.pack(&archive, "secret").unwrap(), 3);
+        assert!(!fs::read(&archive).unwrap().windows(4).any(|w| w == b"1000"));
+
+        let other = InputStore::new(tmp.path().join("other"));
+        assert!(matches!(
+            other.unpack(&archive, "wrong", false),
+            Err(InputsError::DecryptError(_))
+        ));
+        assert_eq!(other.unpack(&archive, "secret", false).unwrap(), 3);
+        assert_eq!(fs::read_to_string(other.path(2023, 25)).unwrap(), "1=-0-2");
+        assert_eq!(fs::read(other.path(2024, 3)).unwrap(), b"mul(\xff,\xfe)");
+        assert_eq!(other.unpack(&archive, "secret", false).unwrap(), 0);
+    }
+}
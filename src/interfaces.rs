@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::requires::Requirement;
+
 /// Metadata of a Day Challege
 #[derive(Debug)]
 pub struct DayChallenge {
@@ -58,6 +60,9 @@ pub struct ProgrammingTemplate {
     /// Programming language
     pub language: String,
 
+    /// Other names of the language
+    pub aliases: Vec<String>,
+
     /// Commands to run
     pub init_commands: Vec<Command>,
 
@@ -69,6 +74,61 @@ pub struct ProgrammingTemplate {
 
     /// Folders to create
     pub folders: Vec<String>,
+
+    /// Tools the language needs, checked before the day is created
+    pub requires: Vec<Requirement>,
+
+    /// Command that runs the solution of the day
+    pub run: Option<Command>,
+
+    /// Command that runs the tests of the day
+    pub test: Option<Command>,
+
+    /// File opened in the editor with the day, relative to its directory
+    pub entry: Option<String>,
+
+    /// Patterns used to extract the answers from the output of `run`
+    pub answer_patterns: AnswerPatterns,
+
+    /// Commands run before and after a day is created or removed
+    pub hooks: Hooks,
+}
+
+/// Commands run in the base directory before and after a day is created or
+/// removed, with the metadata of the day in `AOC_*` environment variables
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Hooks {
+    pub pre_add: Vec<Command>,
+    pub post_add: Vec<Command>,
+    pub pre_remove: Vec<Command>,
+    pub post_remove: Vec<Command>,
+}
+
+impl Hooks {
+    /// Appends the commands of `other` to the ones of each hook
+    pub fn extend(&mut self, other: Hooks) {
+        self.pre_add.extend(other.pre_add);
+        self.post_add.extend(other.post_add);
+        self.pre_remove.extend(other.pre_remove);
+        self.post_remove.extend(other.post_remove);
+    }
+}
+
+/// Regular expressions that extract the answer of each part from the output
+/// of a solution. The first capture group of each pattern is the answer.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AnswerPatterns {
+    pub part1: String,
+    pub part2: String,
+}
+
+impl Default for AnswerPatterns {
+    fn default() -> Self {
+        Self {
+            part1: r"(?im)^\s*part\s*1\s*[:=]\s*(.+?)\s*$".to_string(),
+            part2: r"(?im)^\s*part\s*2\s*[:=]\s*(.+?)\s*$".to_string(),
+        }
+    }
 }
 
 impl Display for DayChallenge {
@@ -1,5 +1,28 @@
+pub mod answers;
+pub mod bench;
 pub mod cli;
+pub mod completions;
+pub mod config;
+pub mod doctor;
+pub mod editor;
+pub mod error;
 pub mod file_handler;
+pub mod git;
+pub mod index;
+pub mod inputs;
 pub mod interfaces;
+pub mod port;
+pub mod readme;
+pub mod reference;
+pub mod registry;
+pub mod rename;
+pub mod requires;
+pub mod scaffold;
+pub mod starters;
+pub mod times;
 pub mod utils;
+pub mod verify;
+pub mod watch;
+pub mod wizard;
+pub mod workspace;
 pub mod yaml_parser;
@@ -1,120 +1,955 @@
 use advent_code_cli::{
-    cli::{Cli, Commands},
+    answers::Answers,
+    bench::{group_timings, read_table, time_day, write_table},
+    cli::{Cli, Commands, InputCommands, TemplateCommands},
+    completions::{write_registration, COMPLETE_VAR},
+    config::Config,
+    doctor::{
+        check_config, check_inputs, check_session, check_structure, check_templates, Severity,
+    },
+    editor::{find_editor, open_day},
+    error::Error,
     file_handler::{AventStructure, BuildError},
-    interfaces::DayChallenge,
-    utils::{list_folder_names, prompt_to_remove_directory},
-    yaml_parser::{parse_values_yml, populate_yml, YamlParserError},
+    git::{
+        commit_day, render_message, repository_root, switch_branch, DEFAULT_COMMIT_MESSAGE,
+        DEFAULT_SOLVE_MESSAGE,
+    },
+    index::{
+        day_to_add, filter_days, languages, most_recent_language, next_missing_day, scan_days,
+        years, DayEntry,
+    },
+    inputs::{default_store_dir, InputOutcome, InputStore, PlaceMode, PASSWORD_VAR},
+    interfaces::{DayChallenge, ProgrammingTemplate},
+    port::copy_day_assets,
+    readme::{update_readme, START_MARKER},
+    reference::write_reference,
+    registry::{TemplateRegistry, TEMPLATES_DIR},
+    rename::{move_day, rerender_files},
+    scaffold::{scaffold_days, ScaffoldOutcome},
+    starters::{find_starter, init_repository, InitStep, STARTERS},
+    times::{print_times, DayTimes, Timestamp},
+    utils::{aoc_today, prompt_for_title, prompt_to_remove_directories, season_for_date},
+    verify::{extract_answers, print_report, run_solution, verify_day},
+    watch::{clear_screen, run_command, Watcher},
+    wizard::{
+        confirm, confirm_plan, is_interactive, password, select_day, select_language,
+        select_starters,
+    },
+    workspace::{register_day, registered_manifests, unregister_day},
+};
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
 };
-use clap::Parser;
-use std::{fs, path::PathBuf};
-
-const TEMPLATE_NAME: &str = "template.yml";
 
 fn main() {
-    let mut cli = Cli::parse();
-    match cli.init() {
-        Ok(_) => {}
-        Err(e) => {
-            eprintln!("Error! {}", e);
-            return;
+    // Answers the completion requests of the shell scripts
+    CompleteEnv::with_factory(Cli::command)
+        .var(COMPLETE_VAR)
+        .complete();
+
+    if let Err(e) = run() {
+        eprintln!("Error! {}", e);
+
+        let mut source = std::error::Error::source(&e);
+        while let Some(cause) = source {
+            eprintln!("  Caused by: {}", cause);
+            source = cause.source();
         }
+
+        std::process::exit(e.exit_code());
+    }
+}
+
+/// Commits the directory of a day when `git_commit` is configured, switching
+/// to the rendered `branch` first
+fn commit_day_changes(
+    config: &Config,
+    challenge: &DayChallenge,
+    path: &Path,
+    files: &[PathBuf],
+    message: &str,
+    branch: Option<&str>,
+) -> Result<(), Error> {
+    if config.git_commit != Some(true) {
+        return Ok(());
+    }
+
+    let repo = repository_root(path)?;
+
+    if let Some(branch) = branch {
+        let branch = render_message(branch, challenge)?;
+        switch_branch(&repo, &branch)?;
+        println!("Switched to the branch {}", branch);
     }
 
-    let template_file = cli
-        .template_file
-        .unwrap_or_else(|| std::path::PathBuf::from(format!("./{}", TEMPLATE_NAME)));
+    let message = render_message(message, challenge)?;
+    if commit_day(path, files, &message)? {
+        println!("Committed \"{}\"", message);
+    } else {
+        println!("Nothing to commit");
+    }
+
+    Ok(())
+}
+
+/// Single day of the year, in the language when given
+fn find_day(
+    structure: &AventStructure,
+    year: u16,
+    day: u8,
+    language: Option<String>,
+) -> Result<DayEntry, Error> {
+    let days = scan_days(&structure.base_directory, &structure.layout)
+        .map_err(|e| Error::io("Unable to read the base directory", e))?;
+    let mut days = filter_days(days, Some(year), language.as_deref())
+        .into_iter()
+        .filter(|d| d.day == day)
+        .collect::<Vec<_>>();
 
-    // Check if the template file exists
-    if !template_file.exists() {
-        eprintln!("Template file does not exist. Create a new one to continue");
-        return;
+    match days.len() {
+        0 => Err(Error::Usage(format!("There is no day {} of {}", day, year))),
+        1 => Ok(days.remove(0)),
+        _ => {
+            let languages = days.iter().map(|d| d.language.as_str()).collect::<Vec<_>>();
+            Err(Error::Usage(format!(
+                "Day {} of {} exists in {}. Please specify the language",
+                day,
+                year,
+                languages.join(", ")
+            )))
+        }
     }
+}
+
+/// Path of a file relative to the directory of the day
+fn file_name(day_path: &Path, file: &Path) -> String {
+    file.strip_prefix(day_path)
+        .unwrap_or(file)
+        .display()
+        .to_string()
+}
+
+/// Opens a day with the editor and the entry file of its template
+fn open_in_editor(config: &Config, entry: Option<&str>, path: &Path) -> Result<(), Error> {
+    let Some(editor) = find_editor(config.editor.as_deref()) else {
+        return Err(Error::Usage(
+            "There is no editor. Set `editor` in the config, $VISUAL or $EDITOR".into(),
+        ));
+    };
+
+    println!("Opening {} with {}", path.display(), editor);
+    open_day(&editor, path, entry)
+        .map_err(|e| Error::io(format!("Unable to open {}", path.display()), e))
+}
+
+fn run() -> Result<(), Error> {
+    let mut cli = Cli::parse();
+    cli.init()?;
 
     let base_directory = cli
         .base_directory
         .unwrap_or_else(|| std::path::PathBuf::from("./"));
 
-    // Check if the base directory exists
-    if !base_directory.exists() {
-        eprintln!("Base directory does not exist. Please select a new base directory");
-        return;
-    }
+    let load_templates =
+        || TemplateRegistry::load(&cli.template_file, &base_directory.join(TEMPLATES_DIR));
+    let read_base_directory = |e| Error::io("Unable to read the base directory", e);
+    // Languages of existing days may be typed as an alias or in another case
+    let day_language = |language: String| {
+        load_templates()
+            .ok()
+            .and_then(|registry| registry.resolve_language(&language).ok())
+            .unwrap_or(language)
+            .to_lowercase()
+    };
 
-    let mut structure = AventStructure::new(base_directory);
+    let default_year = cli.config.year.unwrap_or_else(|| {
+        let (year, month, _) = aoc_today();
+        season_for_date(year, month)
+    });
+
+    let commit_message = cli
+        .config
+        .commit_message
+        .clone()
+        .unwrap_or(DEFAULT_COMMIT_MESSAGE.to_string());
+
+    let input_store = cli
+        .config
+        .input_store
+        .clone()
+        .or_else(default_store_dir)
+        .map(InputStore::new);
+
+    let mut structure = AventStructure::new(base_directory.clone());
+    structure.layout = cli.layout;
 
     match cli.command {
         Commands::List { year, lang } => {
-            if year.is_none() {
-                // List all the years in the base directory
+            let lang = lang.map(day_language);
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map(|days| filter_days(days, year, lang.as_deref()))
+                .map_err(read_base_directory)?;
+
+            let Some(year) = year else {
                 println!("Listing all the years in the base directory: ");
-                list_folder_names(&structure.base_directory);
-                return ();
+                for year in years(&days) {
+                    println!("- {}", year);
+                }
+                return Ok(());
+            };
+
+            if days.is_empty() {
+                match lang {
+                    Some(lang) => println!(
+                        "You do not have entries for the year {} in the language {}...",
+                        year, lang
+                    ),
+                    None => println!("You do not have entries for the year {}...", year),
+                }
+                return Ok(());
+            }
+
+            let langs = languages(&days);
+            let [lang] = langs.as_slice() else {
+                println!("Listing all the languages in the year {}", year);
+                for lang in langs {
+                    println!("- {}", lang);
+                }
+                return Ok(());
+            };
+
+            println!(
+                "Listing all the days from the year {} in the language {}",
+                year, lang
+            );
+            for day in &days {
+                match day.title.as_str() {
+                    "" => println!("- Day {:02}", day.day),
+                    title => println!("- Day {:02}: {}", day.day, title),
+                }
+            }
+        }
+        Commands::Times { year, language } => {
+            let language = language.map(day_language);
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map(|days| filter_days(days, year, language.as_deref()))
+                .map_err(read_base_directory)?;
+
+            // Days created before the times were tracked have none
+            let mut results = vec![];
+            for day in days {
+                let times = DayTimes::load(&day.path)?;
+                if times != DayTimes::default() {
+                    results.push((day, times));
+                }
             }
 
-            let year = year.unwrap();
+            if results.is_empty() {
+                println!("There are no times yet. They are recorded by `add` and `solve`");
+                return Ok(());
+            }
 
-            let year_path = structure.base_directory.join(year.to_string());
+            print_times(&results);
+        }
+        Commands::Verify { year, language } => {
+            let language = language.map(day_language);
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map(|days| filter_days(days, year, language.as_deref()))
+                .map_err(read_base_directory)?;
 
-            if !year_path.exists() {
-                println!("You do not have entries for the year {}...", year);
-                return;
+            if days.is_empty() {
+                println!("There are no days to verify");
+                return Ok(());
             }
 
-            // Check if only one language
+            let registry = load_templates()?;
 
-            let langs = std::fs::read_dir(&year_path)
-                .expect("Expected to be able to read year")
-                .filter_map(|e| {
-                    let path = e.unwrap().path();
-                    if path.is_dir() {
-                        Some(path.file_name().unwrap().to_str().unwrap().to_string())
-                    } else {
-                        None
-                    }
+            let results = days
+                .into_iter()
+                .map(|day| {
+                    let outcome = verify_day(&day, &registry);
+                    (day, outcome)
                 })
-                .collect::<Vec<String>>();
+                .collect::<Vec<_>>();
+
+            print_report(&results);
 
-            if langs.is_empty() {
-                println!("You do not have entries for the year {}...", year);
-                return;
+            let regressions = results
+                .iter()
+                .filter(|(_, outcome)| outcome.is_regression())
+                .count();
+
+            if regressions > 0 {
+                return Err(Error::Regressions(regressions));
+            }
+        }
+        Commands::Bench {
+            year,
+            language,
+            runs,
+            output,
+            baseline,
+        } => {
+            let language = language.map(day_language);
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map(|days| filter_days(days, year, language.as_deref()))
+                .map_err(read_base_directory)?;
+
+            if days.is_empty() {
+                println!("There are no days to benchmark");
+                return Ok(());
             }
 
-            if langs.iter().count() == 1 {
+            let registry = load_templates()?;
+
+            let mut timings = vec![];
+            for day in days {
                 println!(
-                    "Listing all the days from the year {} in the language {}",
-                    year, langs[0]
+                    "Benchmarking {} {} day {:02}...",
+                    day.year, day.language, day.day
                 );
-                let path = year_path.join(&langs[0]);
-                list_folder_names(&path);
+                match time_day(&day, &registry, runs.max(1)) {
+                    Ok(timing) => timings.push((day, timing)),
+                    Err(e) => eprintln!("Skipping day {:02}: {}", day.day, e),
+                }
             }
 
-            if lang.is_none() {
-                // List all the languages in the year
-                println!("Listing all the languages in the year {}", year);
-                list_folder_names(&year_path);
-                return;
+            let output = output.unwrap_or_else(|| base_directory.join("benchmarks"));
+
+            for table in group_timings(timings) {
+                let baseline = match &baseline {
+                    Some(dir) => match read_table(dir, &table.file_stem()) {
+                        Ok(b) => b,
+                        Err(e) => {
+                            eprintln!("Unable to read the baseline: {}", e);
+                            None
+                        }
+                    },
+                    None => None,
+                };
+
+                println!("\n{}", table.to_markdown(baseline.as_ref()));
+
+                write_table(&output, &table, baseline.as_ref()).map_err(|e| {
+                    Error::io(
+                        format!("Unable to write the timing table to {}", output.display()),
+                        e,
+                    )
+                })?;
+            }
+
+            println!("Timing tables written to {}", output.display());
+        }
+        Commands::Template {
+            command: TemplateCommands::List,
+        } => {
+            let registry = load_templates()?;
+
+            for language in registry.languages()? {
+                println!("- {} ({})", language.name, language.source.display());
+                for overridden in language.overrides {
+                    println!("    overrides {}", overridden.display());
+                }
+                for (alias, other) in language.collisions {
+                    println!("    alias {} is also used by the language {}", alias, other);
+                }
+            }
+        }
+        Commands::Input { command } => {
+            let Some(store) = input_store else {
+                return Err(Error::Usage(
+                    "There is no input store. Set input_store in the config".into(),
+                ));
+            };
+            let read_store = |e| Error::io("Unable to access the input store", e);
+            let read_password = |new: bool| match std::env::var(PASSWORD_VAR) {
+                Ok(password) => Ok(password),
+                Err(_) if is_interactive() => password("Password of the inputs", new)
+                    .map_err(|e| Error::io("Unable to read the password", e)),
+                Err(_) => Err(Error::Usage(format!(
+                    "Set {} or run the command in a terminal",
+                    PASSWORD_VAR
+                ))),
+            };
+
+            match command {
+                InputCommands::Store { year, day, file } => {
+                    let content = match &file {
+                        Some(file) => std::fs::read(file).map_err(|e| {
+                            Error::io(format!("Unable to read {}", file.display()), e)
+                        })?,
+                        None => {
+                            let mut content = vec![];
+                            std::io::Read::read_to_end(&mut std::io::stdin(), &mut content)
+                                .map_err(|e| Error::io("Unable to read the input", e))?;
+                            content
+                        }
+                    };
+
+                    let path = store.save(year, day, &content).map_err(read_store)?;
+                    println!("Stored the input in {}", path.display());
+                }
+                InputCommands::Collect { year, language } => {
+                    let language = language.map(day_language);
+                    let days = scan_days(&structure.base_directory, &structure.layout)
+                        .map(|days| filter_days(days, year, language.as_deref()))
+                        .map_err(read_base_directory)?;
+
+                    for day in days {
+                        let outcome = store
+                            .collect(&day.path, day.year, day.day)
+                            .map_err(read_store)?;
+                        println!(
+                            "{} {} day {:02}: {}",
+                            day.year, day.language, day.day, outcome
+                        );
+                    }
+                }
+                InputCommands::Link {
+                    year,
+                    language,
+                    copy,
+                } => {
+                    let language = language.map(day_language);
+                    let days = scan_days(&structure.base_directory, &structure.layout)
+                        .map(|days| filter_days(days, year, language.as_deref()))
+                        .map_err(read_base_directory)?;
+                    let mode = if copy {
+                        PlaceMode::Copy
+                    } else {
+                        PlaceMode::Link
+                    };
+
+                    for day in days {
+                        let outcome = store
+                            .place(&day.path, day.year, day.day, mode)
+                            .map_err(read_store)?;
+                        println!(
+                            "{} {} day {:02}: {}",
+                            day.year, day.language, day.day, outcome
+                        );
+                    }
+                }
+                InputCommands::Pack { output } => {
+                    let output = base_directory.join(output);
+                    let packed = store.pack(&output, &read_password(true)?)?;
+                    println!("Encrypted {} inputs into {}", packed, output.display());
+                }
+                InputCommands::Unpack { archive, force } => {
+                    let archive = base_directory.join(archive);
+                    let unpacked = store.unpack(&archive, &read_password(false)?, force)?;
+                    println!("Stored {} inputs in {}", unpacked, store.root.display());
+                }
+            }
+        }
+        Commands::Init { languages, force } => {
+            let starters = if !languages.is_empty() {
+                languages
+                    .iter()
+                    .map(|l| {
+                        find_starter(l).ok_or_else(|| {
+                            let names = STARTERS.iter().map(|s| s.name).collect::<Vec<_>>();
+                            Error::Usage(format!(
+                                "There is no starter template for {}. Available: {}",
+                                l,
+                                names.join(", ")
+                            ))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+            } else if is_interactive() {
+                let names = STARTERS.iter().map(|s| s.name).collect::<Vec<_>>();
+                let selected = select_starters(&names)
+                    .map_err(|e| Error::io("Unable to read the languages", e))?;
+                selected.into_iter().map(|i| &STARTERS[i]).collect()
+            } else {
+                STARTERS.iter().collect()
+            };
+
+            if starters.is_empty() {
+                return Err(Error::Usage("Select at least one language".into()));
+            }
+
+            let steps = init_repository(&base_directory, &starters, force).map_err(|e| {
+                Error::io(
+                    format!("Unable to initialize {}", base_directory.display()),
+                    e,
+                )
+            })?;
+
+            let mut skipped = false;
+            for step in steps {
+                match step {
+                    InitStep::Created(path) => println!("Created {}", path.display()),
+                    InitStep::Updated(path) => println!("Updated {}", path.display()),
+                    InitStep::Skipped(path) => {
+                        skipped = true;
+                        println!("Kept {} (it already exists)", path.display())
+                    }
+                }
+            }
+
+            if skipped && !force {
+                println!("Use --force to overwrite the template and config files");
+            }
+        }
+        Commands::Completions { shell } => {
+            write_registration(&shell, &mut std::io::stdout())
+                .map_err(|e| Error::io("Unable to write the completions", e))?;
+        }
+        Commands::Reference { output } => {
+            let written = write_reference(&output).map_err(|e| {
+                Error::io(
+                    format!("Unable to write the reference to {}", output.display()),
+                    e,
+                )
+            })?;
+
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Commands::Doctor => {
+            let current_dir = std::env::current_dir()
+                .map_err(|e| Error::io("Unable to read the current directory", e))?;
+            let days = scan_days(&structure.base_directory, &structure.layout).unwrap_or_default();
+
+            let sections = [
+                ("Config", check_config(&current_dir)),
+                (
+                    "Templates",
+                    check_templates(
+                        &cli.template_file,
+                        &base_directory.join(TEMPLATES_DIR),
+                        default_year,
+                    ),
+                ),
+                (
+                    "Days",
+                    check_structure(&structure.base_directory, &structure.layout),
+                ),
+                ("Inputs", check_inputs(&days)),
+                ("Session", vec![check_session(&cli.config)]),
+            ];
+
+            let mut problems = 0;
+            for (title, checks) in sections {
+                println!("{}", title);
+                for check in checks {
+                    if check.severity == Severity::Error {
+                        problems += 1;
+                    }
+                    println!("  {}", check.to_string().replace('\n', "\n  "));
+                }
+                println!();
+            }
+
+            if problems > 0 {
+                return Err(Error::Unhealthy(problems));
+            }
+
+            println!("Everything is ready");
+        }
+        Commands::Readme { file } => {
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map_err(read_base_directory)?;
+
+            let readme = file.unwrap_or_else(|| base_directory.join("README.md"));
+
+            update_readme(&readme, &days)
+                .map_err(|e| Error::io(format!("Unable to update {}", readme.display()), e))?;
+
+            println!("Updated the progress of {}", readme.display());
+        }
+        Commands::Port {
+            year,
+            day,
+            from,
+            to,
+        } => {
+            let registry = load_templates()?;
+            let from = day_language(from);
+            let to = registry.resolve_language(&to)?;
+
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map_err(read_base_directory)?;
+            let find_day = |language: &str| {
+                filter_days(days.clone(), Some(year), Some(language))
+                    .into_iter()
+                    .find(|d| d.day == day)
+            };
+
+            let Some(source) = find_day(&from) else {
+                return Err(Error::Usage(format!(
+                    "There is no day {} of {} in {}",
+                    day, year, from
+                )));
+            };
+
+            if let Some(existing) = find_day(&to) {
+                return Err(Error::Usage(format!(
+                    "The day already exists in {}: {}",
+                    to,
+                    existing.path.display()
+                )));
+            }
+
+            let day_challenge = DayChallenge::new(day, year, to, source.title.clone());
+            let programming_template = registry.template_for_challenge(&day_challenge)?;
+            let path = structure.add_day(&day_challenge, &programming_template)?;
+
+            let copied = copy_day_assets(&source.path, &path).map_err(|e| {
+                Error::io(
+                    format!("Unable to copy the files of {}", source.path.display()),
+                    e,
+                )
+            })?;
+
+            println!("Successfully ported the day");
+            println!("Path: {}", path.display());
+            for file in copied {
+                println!("  copied {}", file.display());
+            }
+
+            // Days ported from a day without an input link to the store too
+            if let Some(store) = &input_store {
+                let outcome = store
+                    .place(&path, year, day, PlaceMode::Link)
+                    .map_err(|e| Error::io("Unable to link the input", e))?;
+                if let InputOutcome::Linked(stored) = outcome {
+                    println!("Input: {}", stored.display());
+                }
+            }
+
+            let manifests = registered_manifests(structure.root_directory(), &path);
+            commit_day_changes(
+                &cli.config,
+                &day_challenge,
+                &path,
+                &manifests,
+                &commit_message,
+                None,
+            )?;
+        }
+        Commands::Rename {
+            year,
+            language,
+            day,
+            title,
+            render,
+            yes,
+        } => {
+            let language = day_language(language);
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map_err(read_base_directory)?;
+
+            let Some(entry) = filter_days(days, Some(year), Some(&language))
+                .into_iter()
+                .find(|d| d.day == day)
+            else {
+                return Err(Error::Usage(format!(
+                    "There is no day {} of {} in {}",
+                    day, year, language
+                )));
+            };
+
+            let old_challenge = entry.challenge();
+            let new_challenge = DayChallenge::new(day, year, entry.language.clone(), title);
+            let path = structure
+                .base_directory
+                .join(structure.layout.day_path(&new_challenge));
+
+            if path != entry.path {
+                move_day(&entry.path, &path).map_err(|e| {
+                    Error::io(format!("Unable to move {}", entry.path.display()), e)
+                })?;
+                println!("Moved {} to {}", entry.path.display(), path.display());
+
+                // The workspace manifests follow the day to its new directory
+                let root = structure.root_directory();
+                if !unregister_day(root, &entry.path)
+                    .map_err(BuildError::from)?
+                    .is_empty()
+                {
+                    register_day(root, &path).map_err(BuildError::from)?;
+                }
+            } else {
+                println!("The directory of the day does not change");
+            }
+
+            if render {
+                let registry = load_templates()?;
+                let rerenders = rerender_files(
+                    &path,
+                    &registry.template_for_challenge(&old_challenge)?,
+                    &registry.template_for_challenge(&new_challenge)?,
+                )
+                .map_err(|e| Error::io("Unable to read the files of the day", e))?;
+
+                if rerenders.is_empty() {
+                    println!("No template file embeds the title");
+                } else {
+                    for rerender in &rerenders {
+                        print!("\n{}", rerender.diff());
+                    }
+                    println!();
+
+                    let apply = yes
+                        || (is_interactive()
+                            && confirm("Apply these changes?")
+                                .map_err(|e| Error::io("Unable to confirm", e))?);
+
+                    if apply {
+                        for rerender in &rerenders {
+                            rerender.apply().map_err(|e| {
+                                Error::io(format!("Unable to write {}", rerender.path.display()), e)
+                            })?;
+                        }
+                        println!("Updated {} files", rerenders.len());
+                    } else {
+                        println!("Files left untouched. Use --yes to apply the changes");
+                    }
+                }
             }
 
-            let lang = lang.unwrap();
+            // The progress table links to the directory of the day
+            let readme = base_directory.join("README.md");
+            if std::fs::read_to_string(&readme).is_ok_and(|c| c.contains(START_MARKER)) {
+                let days = scan_days(&structure.base_directory, &structure.layout)
+                    .map_err(read_base_directory)?;
+                update_readme(&readme, &days)
+                    .map_err(|e| Error::io(format!("Unable to update {}", readme.display()), e))?;
+                println!("Updated the progress of {}", readme.display());
+            }
+        }
+        Commands::Open {
+            year,
+            day,
+            language,
+        } => {
+            let entry = find_day(&structure, year, day, language.map(day_language))?;
+
+            // Days of languages that are no longer in the template open without an entry
+            let template = load_templates()?
+                .template_for_challenge(&entry.challenge())
+                .ok();
+            open_in_editor(
+                &cli.config,
+                template.as_ref().and_then(|t| t.entry.as_deref()),
+                &entry.path,
+            )?;
+        }
+        Commands::Watch {
+            year,
+            day,
+            language,
+            run,
+            debounce,
+        } => {
+            let entry = find_day(&structure, year, day, language.map(day_language))?;
+            let template = load_templates()?.template_for_challenge(&entry.challenge())?;
+
+            let (command, is_run) = match (run, template.test, template.run) {
+                (false, Some(test), _) => (test, false),
+                (_, _, Some(run)) => (run, true),
+                (true, _, None) => {
+                    return Err(Error::Usage(format!(
+                        "There is no run command for {}",
+                        entry.language
+                    )))
+                }
+                (false, None, None) => {
+                    return Err(Error::Usage(format!(
+                        "There is no test or run command for {}",
+                        entry.language
+                    )))
+                }
+            };
 
-            let lang_path = year_path.join(&lang);
+            let name = format!("{} {} day {:02}", entry.year, entry.language, entry.day);
+            let watch_error = |e| Error::io(format!("Unable to watch {}", entry.path.display()), e);
+            let mut watcher =
+                Watcher::new(&entry.path, Duration::from_millis(debounce)).map_err(watch_error)?;
+            let mut changed: Vec<PathBuf> = vec![];
 
-            if !lang_path.exists() {
+            loop {
+                clear_screen();
+                println!("{}: {}", name, command.command);
+                for file in &changed {
+                    println!("  changed {}", file_name(&entry.path, file));
+                }
+                println!();
+
+                let report = run_command(&command.command, &entry.path)
+                    .map_err(|e| Error::io(format!("Unable to run `{}`", command.command), e))?;
+                println!("\n{}", report);
+
+                // Runs of the solution count as the first run of the parts they answer
+                if is_run {
+                    if let Ok(found) = extract_answers(&template.answer_patterns, &report.stdout) {
+                        DayTimes::update(&entry.path, false, |times| {
+                            times.record_runs(&found, Timestamp::now())
+                        })?;
+                    }
+                }
                 println!(
-                    "You do not have entries for the year {} in the language {}...",
-                    year, lang
+                    "Watching {} for changes. Press Ctrl-C to stop",
+                    entry.path.display()
                 );
-                return;
+
+                // Files written by the command itself do not trigger a new run
+                watcher.refresh().map_err(watch_error)?;
+                changed = watcher.wait().map_err(watch_error)?;
+            }
+        }
+        Commands::Solve {
+            year,
+            language,
+            day,
+            part1,
+            part2,
+        } => {
+            let language = day_language(language);
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map_err(read_base_directory)?;
+
+            let Some(entry) = filter_days(days, Some(year), Some(&language))
+                .into_iter()
+                .find(|d| d.day == day)
+            else {
+                return Err(Error::Usage(format!(
+                    "There is no day {} of {} in {}",
+                    day, year, language
+                )));
+            };
+
+            let recorded = Answers::load(&entry.path)?;
+
+            // Only run the solution when an answer is not given
+            let [found1, found2] = if part1.is_none() || part2.is_none() {
+                let registry = load_templates()?;
+                match run_solution(&entry, &registry) {
+                    Ok(Some(found)) => found,
+                    Ok(None) => {
+                        println!("No run command for {}", entry.language);
+                        [None, None]
+                    }
+                    Err(e) => return Err(Error::Usage(e)),
+                }
+            } else {
+                [None, None]
+            };
+
+            let unsolved = [recorded.part1.is_none(), recorded.part2.is_none()];
+            let answers = Answers {
+                part1: part1.or(found1).or(recorded.part1),
+                part2: part2.or(found2).or(recorded.part2),
+            };
+
+            if answers == Answers::default() {
+                return Err(Error::Usage(
+                    "No answers found. Give them with --part1 and --part2".into(),
+                ));
+            }
+
+            answers.save(&entry.path)?;
+            for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+                println!("Part {}: {}", part, answer.as_deref().unwrap_or("-"));
             }
 
-            // List all the days in the language
+            // A part is solved when its first answer is recorded
+            let now = Timestamp::now();
+            DayTimes::update(&entry.path, true, |times| {
+                for part in [1, 2] {
+                    if unsolved[part as usize - 1] && answers.part(part).is_some() {
+                        times.record_solved(part, now);
+                    }
+                }
+            })?;
+
+            let solve_message = cli
+                .config
+                .solve_message
+                .clone()
+                .unwrap_or(DEFAULT_SOLVE_MESSAGE.to_string());
+            commit_day_changes(
+                &cli.config,
+                &entry.challenge(),
+                &entry.path,
+                &[],
+                &solve_message,
+                None,
+            )?;
+        }
+        Commands::ScaffoldYear {
+            year,
+            language,
+            days,
+            title,
+            jobs,
+        } => {
+            let registry = load_templates()?;
+            let year = year.unwrap_or(default_year);
+
+            let languages = registry.language_names()?;
+            let language = match (
+                language.or(cli.config.language.clone()),
+                languages.as_slice(),
+            ) {
+                (Some(language), _) => registry.resolve_language(&language)?,
+                (None, [only]) => only.clone(),
+                (None, _) => {
+                    return Err(Error::Usage(
+                        "There is no default language. Please specify one".into(),
+                    ))
+                }
+            };
+
+            let existing = scan_days(&structure.base_directory, &structure.layout)
+                .map_err(read_base_directory)?;
+            let challenges = days
+                .0
+                .into_iter()
+                .map(|day| DayChallenge::new(day, year, language.clone(), title.clone()))
+                .collect();
+
+            let results = scaffold_days(&structure, &registry, challenges, &existing, jobs);
+
+            let (mut created, mut skipped, mut failed) = (0, 0, 0);
+            for (challenge, outcome) in &results {
+                match outcome {
+                    ScaffoldOutcome::Created(_) => created += 1,
+                    ScaffoldOutcome::Skipped(_) => skipped += 1,
+                    ScaffoldOutcome::Failed(_) => failed += 1,
+                }
+                println!("Day {:02}: {}", challenge.day, outcome);
+            }
 
             println!(
-                "Listing all the days from the year {} in the language {}",
-                year, lang
+                "\n{} {}: {} created, {} skipped, {} failed",
+                year, language, created, skipped, failed
             );
 
-            list_folder_names(&lang_path);
+            for (challenge, outcome) in &results {
+                if let ScaffoldOutcome::Created(path) = outcome {
+                    let manifests = registered_manifests(structure.root_directory(), path);
+                    commit_day_changes(
+                        &cli.config,
+                        challenge,
+                        path,
+                        &manifests,
+                        &commit_message,
+                        None,
+                    )?;
+                }
+            }
+
+            if failed > 0 {
+                return Err(Error::FailedDays(failed));
+            }
         }
         Commands::Remove {
             day,
@@ -123,106 +958,182 @@ fn main() {
             force,
         } => {
             let force = force.unwrap_or(false);
+            let language = language.map(day_language);
 
-            let dir: PathBuf;
-            match (year, day, language) {
+            match (year, day, &language) {
                 (None, None, None) => {
-                    eprintln!("You need to specify: year, day, language");
-                    return;
+                    return Err(Error::Usage(
+                        "You need to specify: year, day, language".into(),
+                    ))
                 }
-                (Some(year), None, None) => {
-                    dir = structure.base_directory.join(year.to_string());
+                (Some(_), None, Some(_)) => {
+                    return Err(Error::Usage(
+                        "You need to specify the day to remove a language".into(),
+                    ))
                 }
-                (Some(year), Some(day), None) => {
-                    dir = structure
-                        .base_directory
-                        .join(year.to_string())
-                        .join(day.to_string());
+                (None, _, _) => {
+                    return Err(Error::Usage(
+                        "You need to specify the year to remove a day".into(),
+                    ))
                 }
-                (Some(year), Some(day), Some(language)) => {
-                    dir = structure
-                        .base_directory
-                        .join(year.to_string())
-                        .join(day.to_string())
-                        .join(language);
-                }
-                (Some(_), None, Some(_)) => unreachable!(),
-                (None, _, _) => unreachable!(),
-            };
+                _ => {}
+            }
+
+            let days = scan_days(&structure.base_directory, &structure.layout)
+                .map(|days| filter_days(days, year, language.as_deref()))
+                .map_err(read_base_directory)?
+                .into_iter()
+                .filter(|d| day.is_none_or(|day| d.day == day))
+                .collect::<Vec<_>>();
+
+            if days.is_empty() {
+                return Err(Error::Usage("There are no days to remove".into()));
+            }
 
-            if !dir.exists() {
-                eprintln!("The directory does not exist");
-                return;
+            let paths = days.iter().map(|d| d.path.clone()).collect::<Vec<_>>();
+            if !prompt_to_remove_directories(&paths, force)
+                .map_err(|e| Error::io("Unable to confirm", e))?
+            {
+                return Ok(());
             }
 
-            prompt_to_remove_directory(&dir, force);
+            let registry = load_templates()?;
+            for day in days {
+                // Days of languages that are no longer in the template only run the global hooks
+                let challenge = day.challenge();
+                let template = match registry.template_for_challenge(&challenge) {
+                    Ok(template) => template,
+                    Err(_) => ProgrammingTemplate {
+                        hooks: registry.global_hooks(&challenge)?,
+                        ..Default::default()
+                    },
+                };
+
+                structure.remove_day(&challenge, &day.path, &template)?;
+                println!("Removed {}", day.path.display());
+            }
         }
         Commands::Add {
             day,
             year,
             language,
             title,
+            interactive,
+            open,
         } => {
-            let day_challenge = DayChallenge {
-                day,
-                year,
-                language,
-                title,
+            let registry = load_templates()?;
+
+            let wizard = is_interactive() && (interactive || day.is_none());
+            let days = scan_days(&structure.base_directory, &structure.layout).unwrap_or_default();
+            let languages = registry.language_names().unwrap_or_default();
+
+            let year = year.unwrap_or(default_year);
+
+            let default_language = cli
+                .config
+                .language
+                .clone()
+                .or_else(|| most_recent_language(&days))
+                .or_else(|| match languages.as_slice() {
+                    [only] => Some(only.clone()),
+                    _ => None,
+                });
+
+            let language = match (language, default_language) {
+                (Some(language), _) => language,
+                (None, default) if wizard && !languages.is_empty() => {
+                    let default = default.and_then(|d| registry.resolve_language(&d).ok());
+                    match select_language(&languages, default.as_deref()) {
+                        Ok(language) => language,
+                        Err(e) => return Err(Error::io("Unable to read the language", e)),
+                    }
+                }
+                (None, Some(default)) => default,
+                (None, None) => {
+                    return Err(Error::Usage(
+                        "There is no default language. Please specify one".into(),
+                    ))
+                }
             };
+            let language = registry.resolve_language(&language)?;
 
-            let template_content =
-                fs::read_to_string(&template_file).expect("Unable to read template file");
+            let day = match day {
+                Some(day) => Some(day),
+                None if wizard => match next_missing_day(&days, year, &language) {
+                    Some(next_day) => {
+                        let existing = filter_days(days.clone(), Some(year), Some(&language))
+                            .iter()
+                            .map(|d| d.day)
+                            .collect::<Vec<u8>>();
 
-            let populated_template = match populate_yml(&template_content, &day_challenge) {
-                Ok(template) => template,
-                Err(e) => match e {
-                    YamlParserError::TeraError(e) => {
-                        panic!("Error! {}", e);
+                        match select_day(&existing, next_day) {
+                            Ok(day) => Some(day),
+                            Err(e) => return Err(Error::io("Unable to read the day", e)),
+                        }
                     }
-                    _ => unreachable!(),
+                    None => None,
+                },
+                None => None,
+            };
+            let day = day_to_add(&days, year, &language, day).map_err(Error::Usage)?;
+
+            let title = match title {
+                Some(title) => title,
+                None => match prompt_for_title(day, year) {
+                    Ok(title) => title,
+                    Err(e) => return Err(Error::io("Unable to read the title", e)),
                 },
             };
 
-            let programming_template =
-                parse_values_yml(&populated_template, &day_challenge.language);
+            let day_challenge = DayChallenge::new(day, year, language, title);
 
-            if let Err(e) = programming_template {
-                match e {
-                    YamlParserError::NoLanguagesProvided => {
-                        panic!("No languages provided in the template file");
-                    }
-                    YamlParserError::NoLanguageFound(l) => {
-                        panic!("No language found for {} in template file", l);
-                    }
-                    YamlParserError::YamlError(e) => {
-                        panic!("YamlError! {}", e);
-                    }
-                    YamlParserError::BadFormat(m) => {
-                        panic!("Bad format! {}", m);
+            let programming_template = registry.template_for_challenge(&day_challenge)?;
+
+            if wizard {
+                let path = structure
+                    .base_directory
+                    .join(structure.layout.day_path(&day_challenge));
+
+                match confirm_plan(&day_challenge, &programming_template, &path) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        println!("Canceling operation");
+                        return Ok(());
                     }
-                    YamlParserError::TeraError(_) => unreachable!(),
+                    Err(e) => return Err(Error::io("Unable to confirm", e)),
                 }
             }
 
-            let programming_template = programming_template.unwrap();
+            let path = structure.add_day(&day_challenge, &programming_template)?;
+            DayTimes::update(&path, true, |times| times.created = Some(Timestamp::now()))?;
+
+            println!("Successfully created the directory structure");
+            println!("Path: {}", path.display());
 
-            match structure.add_day(&day_challenge, &programming_template) {
-                Ok(p) => {
-                    println!("Successfully created the directory structure");
-                    println!("Path: {}", p.as_os_str().to_str().unwrap());
+            if let Some(store) = &input_store {
+                let outcome = store
+                    .place(&path, year, day, PlaceMode::Link)
+                    .map_err(|e| Error::io("Unable to link the input", e))?;
+                if let InputOutcome::Linked(stored) = outcome {
+                    println!("Input: {}", stored.display());
                 }
-                Err(e) => match e {
-                    BuildError::FileError => {
-                        panic!("FileError: Problems building the files ");
-                    }
-                    BuildError::TemplateError(m) => {
-                        panic!("TemplateError: {}", m);
-                    }
-                    BuildError::DirectoryError => {
-                        panic!("DirectoryError: Problems building the directories");
-                    }
-                },
+            }
+
+            let manifests = registered_manifests(structure.root_directory(), &path);
+            commit_day_changes(
+                &cli.config,
+                &day_challenge,
+                &path,
+                &manifests,
+                &commit_message,
+                cli.config.git_branch.as_deref(),
+            )?;
+
+            if open {
+                open_in_editor(&cli.config, programming_template.entry.as_deref(), &path)?;
             }
         }
     }
+
+    Ok(())
 }
@@ -0,0 +1,117 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answers::ANSWERS_FILE,
+    inputs::{symlink, INPUT_FILE},
+};
+
+/// Files of a day that do not depend on the language
+pub const PORTED_FILES: &[&str] = &[INPUT_FILE, "demo-input.txt", ANSWERS_FILE];
+
+/// Notes are kept in files whose name starts with this prefix
+pub const NOTES_PREFIX: &str = "notes";
+
+fn is_ported_file(name: &str) -> bool {
+    PORTED_FILES.contains(&name) || name.to_lowercase().starts_with(NOTES_PREFIX)
+}
+
+/// Copies the inputs, notes and recorded answers of a day into the directory
+/// of the same day in another language. Files created by the template are
+/// overwritten, and links, like the inputs of the store, stay links.
+/// Returns the copied files
+pub fn copy_day_assets(from: &Path, to: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut copied = vec![];
+
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        if !path.is_file() || !is_ported_file(name) {
+            continue;
+        }
+
+        let target = to.join(name);
+        if fs::symlink_metadata(&path)?.is_symlink() {
+            match fs::remove_file(&target) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+            // Relative links are resolved from the directory of the source day
+            symlink(&from.join(fs::read_link(&path)?), &target)?;
+        } else {
+            fs::copy(&path, &target)?;
+        }
+        copied.push(target);
+    }
+
+    copied.sort();
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    use crate::inputs::InputStore;
+
+    #[test]
+    fn test_copy_day_assets() {
+        let tmp = TempDir::new("testing_port").unwrap();
+        let from = tmp.path().join("rust");
+        let to = tmp.path().join("python");
+        fs::create_dir_all(from.join("src")).unwrap();
+        fs::create_dir_all(&to).unwrap();
+
+        fs::write(from.join("input.txt"), "1\n2\n").unwrap();
+        fs::write(from.join("demo-input.txt"), "1\n").unwrap();
+        fs::write(from.join(ANSWERS_FILE), "part1: 3\n").unwrap();
+        fs::write(from.join("notes-day-01.txt"), "use a heap").unwrap();
+        fs::write(from.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(from.join("src/main.rs"), "fn main() {}").unwrap();
+        // Created empty by the template of the new language
+        fs::write(to.join("input.txt"), "").unwrap();
+
+        let copied = copy_day_assets(&from, &to).unwrap();
+
+        assert_eq!(
+            copied,
+            vec![
+                to.join(ANSWERS_FILE),
+                to.join("demo-input.txt"),
+                to.join("input.txt"),
+                to.join("notes-day-01.txt"),
+            ]
+        );
+        assert_eq!(fs::read_to_string(to.join("input.txt")).unwrap(), "1\n2\n");
+        assert!(!to.join("Cargo.toml").exists());
+        assert!(!to.join("src").exists());
+    }
+
+    #[test]
+    fn test_copy_day_assets_keeps_store_links() {
+        let tmp = TempDir::new("testing_port").unwrap();
+        let store = InputStore::new(tmp.path().join("store"));
+        let from = tmp.path().join("rust");
+        let to = tmp.path().join("python");
+        fs::create_dir_all(&from).unwrap();
+        fs::create_dir_all(&to).unwrap();
+
+        fs::write(from.join(INPUT_FILE), "1abc2").unwrap();
+        store.collect(&from, 2023, 1).unwrap();
+        fs::write(to.join(INPUT_FILE), "").unwrap();
+
+        copy_day_assets(&from, &to).unwrap();
+
+        let input = to.join(INPUT_FILE);
+        assert!(fs::symlink_metadata(&input).unwrap().is_symlink());
+        assert_eq!(fs::read_link(&input).unwrap(), store.path(2023, 1));
+        assert_eq!(fs::read_to_string(&input).unwrap(), "1abc2");
+    }
+}
@@ -0,0 +1,229 @@
+use std::{collections::BTreeSet, fs, io, path::Path};
+
+use crate::{answers::Answers, index::DayEntry};
+
+/// Marks the beginning of the region of the README managed by the cli
+pub const START_MARKER: &str = "<!-- advent-code-cli:progress:start -->";
+/// Marks the end of the region of the README managed by the cli
+pub const END_MARKER: &str = "<!-- advent-code-cli:progress:end -->";
+
+/// Number of stars earned in a day, based on its recorded answers
+pub fn stars(answers: &Answers) -> usize {
+    [&answers.part1, &answers.part2]
+        .iter()
+        .filter(|a| a.is_some())
+        .count()
+}
+
+/// Link to the puzzle page of a day
+pub fn puzzle_url(year: u16, day: u8) -> String {
+    format!("https://adventofcode.com/{}/day/{}", year, day)
+}
+
+/// Renders a table per year with a row per day and a column per language.
+/// Links are relative to `readme_dir`
+pub fn render_progress(days: &[(DayEntry, Answers)], readme_dir: &Path) -> String {
+    let mut out = String::new();
+
+    let years = days.iter().map(|(d, _)| d.year).collect::<BTreeSet<_>>();
+
+    for year in years.iter().rev() {
+        let year_days = days
+            .iter()
+            .filter(|(d, _)| d.year == *year)
+            .collect::<Vec<_>>();
+        let languages = year_days
+            .iter()
+            .map(|(d, _)| d.language.as_str())
+            .collect::<BTreeSet<_>>();
+        let numbers = year_days
+            .iter()
+            .map(|(d, _)| d.day)
+            .collect::<BTreeSet<_>>();
+
+        let total_stars: usize = numbers
+            .iter()
+            .map(|n| {
+                year_days
+                    .iter()
+                    .filter(|(d, _)| d.day == *n)
+                    .map(|(_, a)| stars(a))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .sum();
+
+        out.push_str(&format!("## {} ({} ⭐)\n\n", year, total_stars));
+        out.push_str("| Day | Puzzle |");
+        for language in &languages {
+            out.push_str(&format!(" {} |", language));
+        }
+        out.push_str("\n| --- | ------ |");
+        for language in &languages {
+            out.push_str(&format!(" {} |", "-".repeat(language.len().max(3))));
+        }
+        out.push('\n');
+
+        for number in &numbers {
+            let same_day = year_days
+                .iter()
+                .filter(|(d, _)| d.day == *number)
+                .collect::<Vec<_>>();
+
+            out.push_str(&format!(
+                "| {:02} | [{}]({}) |",
+                number,
+                same_day[0].0.title,
+                puzzle_url(*year, *number)
+            ));
+
+            for language in &languages {
+                match same_day.iter().find(|(d, _)| d.language == *language) {
+                    Some((d, a)) => {
+                        let link = d.path.strip_prefix(readme_dir).unwrap_or(&d.path);
+                        let text = match stars(a) {
+                            0 => "started".to_string(),
+                            n => "⭐".repeat(n),
+                        };
+                        out.push_str(&format!(" [{}]({}) |", text, link.display()));
+                    }
+                    None => out.push_str(" |"),
+                }
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Replaces the content between the markers with the region.
+/// If the markers are not found, the region is appended at the end
+pub fn replace_region(content: &str, region: &str) -> String {
+    let block = format!("{}\n{}{}", START_MARKER, region, END_MARKER);
+
+    if let (Some(start), Some(end)) = (content.find(START_MARKER), content.find(END_MARKER)) {
+        if start < end {
+            return format!(
+                "{}{}{}",
+                &content[..start],
+                block,
+                &content[end + END_MARKER.len()..]
+            );
+        }
+    }
+
+    if content.is_empty() {
+        return format!("{}\n", block);
+    }
+
+    let separator = if content.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!("{}{}{}\n", content, separator, block)
+}
+
+/// Writes the progress tables of the days into the README
+/// Creates the README if it does not exist
+pub fn update_readme(readme: &Path, days: &[DayEntry]) -> io::Result<()> {
+    let readme_dir = readme.parent().unwrap_or(Path::new(""));
+    let readme_dir = readme_dir
+        .canonicalize()
+        .unwrap_or(readme_dir.to_path_buf());
+
+    let days = days
+        .iter()
+        .map(|d| {
+            let answers = Answers::load(&d.path).unwrap_or_default();
+            let mut d = d.clone();
+            d.path = d.path.canonicalize().unwrap_or(d.path);
+            (d, answers)
+        })
+        .collect::<Vec<_>>();
+
+    let content = if readme.exists() {
+        fs::read_to_string(readme)?
+    } else {
+        String::new()
+    };
+
+    let updated = replace_region(&content, &render_progress(&days, &readme_dir));
+
+    if updated != content {
+        fs::write(readme, updated)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn entry(day: u8, language: &str) -> DayEntry {
+        DayEntry {
+            day,
+            year: 2022,
+            language: language.to_string(),
+            title: "calories".to_string(),
+            path: PathBuf::from(format!(
+                "/repo/src/2022/{}/Day-{:02}-calories",
+                language, day
+            )),
+        }
+    }
+
+    #[test]
+    fn test_render_progress() {
+        let days = vec![
+            (
+                entry(1, "rust"),
+                Answers {
+                    part1: Some("1".into()),
+                    part2: Some("2".into()),
+                },
+            ),
+            (entry(1, "python"), Answers::default()),
+        ];
+
+        let table = render_progress(&days, Path::new("/repo"));
+
+        assert_eq!(
+            table,
+            "## 2022 (2 ⭐)\n\n\
+             | Day | Puzzle | python | rust |\n\
+             | --- | ------ | ------ | ---- |\n\
+             | 01 | [calories](https://adventofcode.com/2022/day/1) | \
+             [started](src/2022/python/Day-01-calories) | \
+             [⭐⭐](src/2022/rust/Day-01-calories) |\n\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_region_is_idempotent() {
+        let content = "# My solutions\n\nSome text\n";
+
+        let once = replace_region(content, "table\n");
+        assert_eq!(
+            once,
+            format!(
+                "# My solutions\n\nSome text\n\n{}\ntable\n{}\n",
+                START_MARKER, END_MARKER
+            )
+        );
+
+        let twice = replace_region(&once, "table\n");
+        assert_eq!(once, twice);
+
+        let updated = replace_region(&format!("{}footer\n", once), "new table\n");
+        assert!(updated.starts_with("# My solutions\n\nSome text\n\n"));
+        assert!(updated.contains("new table\n"));
+        assert!(!updated.contains("\ntable\n"));
+        assert!(updated.ends_with(&format!("{}\nfooter\n", END_MARKER)));
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::{Arg, Command, CommandFactory};
+use clap_mangen::Man;
+use roff::{bold, roman, Roff};
+
+use crate::cli::Cli;
+
+/// File of the markdown reference inside the output directory
+pub const MARKDOWN_FILE: &str = "reference.md";
+
+/// Keys of a language in the template file
+pub const TEMPLATE_KEYS: &[(&str, &str)] = &[
+    (
+        "aliases",
+        "Other names of the language. Languages are matched ignoring the case",
+    ),
+    (
+        "init_commands",
+        "Commands run in the directory of the day before the files are written",
+    ),
+    (
+        "commands",
+        "Commands run after the folders and files are created",
+    ),
+    (
+        "files",
+        "Files to create. Either a name or a `name` with its `content`",
+    ),
+    ("folders", "Folders to create"),
+    (
+        "requires",
+        "Tools the language needs, like `cargo` or `rustc>=1.70`. Checked before a day is \
+         created, reading the version from `<tool> --version`",
+    ),
+    (
+        "run",
+        "Command that runs the solution, used by `verify` and `bench`",
+    ),
+    ("test", "Command that runs the tests of the day"),
+    (
+        "entry",
+        "File opened in the editor with the day, relative to its directory",
+    ),
+    (
+        "hooks",
+        "`pre_add`, `post_add`, `pre_remove` and `post_remove` commands, run in the base \
+         directory with the `AOC_*` variables. Top-level hooks run for every language",
+    ),
+    (
+        "answers",
+        "`part1` and `part2` patterns that read the answers from the output of `run`. \
+         The first capture group is the answer",
+    ),
+];
+
+/// Variables available to the template file
+pub const TEMPLATE_VARIABLES: &[(&str, &str)] = &[
+    ("day", "Day of the advent"),
+    ("year", "Year of the advent"),
+    ("language", "Language of the template"),
+    ("title", "Title of the challenge"),
+];
+
+const TEMPLATE_EXAMPLE: &str = "\
+rust:
+  aliases: [rs]
+  requires: [cargo, rustc>=1.70]
+  init_commands:
+    - cargo init --vcs none -q
+  files:
+    - input.txt
+    - name: notes.md
+      content: |
+        # Day {{ day }}: {{ title }}
+  folders:
+    - docs/
+  run: cargo run -q --release
+  test: cargo test -q
+  entry: src/main.rs
+";
+
+fn command() -> Command {
+    let mut command = Cli::command();
+    command.build();
+    command
+}
+
+fn markdown_arg(arg: &Arg) -> String {
+    let name = match (arg.get_short(), arg.get_long()) {
+        (Some(short), Some(long)) => format!("`-{}`, `--{}`", short, long),
+        (Some(short), None) => format!("`-{}`", short),
+        (None, Some(long)) => format!("`--{}`", long),
+        (None, None) => format!("`<{}>`", arg.get_id().as_str().to_uppercase()),
+    };
+
+    let mut line = format!("- {}", name);
+    if !arg.is_positional() && arg.get_action().takes_values() {
+        let value = arg
+            .get_value_names()
+            .and_then(|names| names.first())
+            .map_or_else(|| arg.get_id().as_str().to_uppercase(), |n| n.to_string());
+        line = format!("{} `<{}>`", line, value);
+    }
+    if let Some(help) = arg.get_help() {
+        line.push_str(&format!(": {}", help.to_string().replace('\n', " ")));
+    }
+
+    let defaults = arg
+        .get_default_values()
+        .iter()
+        .map(|v| v.to_string_lossy().into_owned())
+        .collect::<Vec<String>>();
+    if !defaults.is_empty() && arg.get_action().takes_values() {
+        line.push_str(&format!(" (default: `{}`)", defaults.join(", ")));
+    }
+
+    let possible = arg
+        .get_possible_values()
+        .iter()
+        .map(|v| format!("`{}`", v.get_name()))
+        .collect::<Vec<String>>();
+    if !possible.is_empty() && arg.get_action().takes_values() {
+        line.push_str(&format!(" [values: {}]", possible.join(", ")));
+    }
+
+    line + "\n"
+}
+
+fn markdown_command(command: &Command, depth: usize, out: &mut String) {
+    let name = command.get_bin_name().unwrap_or(command.get_name());
+    out.push_str(&format!("{} `{}`\n\n", "#".repeat(depth), name));
+
+    if let Some(about) = command.get_long_about().or(command.get_about()) {
+        out.push_str(&format!("{}\n\n", about));
+    }
+
+    let usage = command.clone().render_usage().to_string();
+    out.push_str(&format!(
+        "```text\n{}\n```\n\n",
+        usage.trim_start_matches("Usage: ")
+    ));
+
+    let args = command
+        .get_arguments()
+        .filter(|a| !a.is_hide_set())
+        .collect::<Vec<&Arg>>();
+
+    for (title, positional) in [("Arguments", true), ("Options", false)] {
+        let section = args
+            .iter()
+            .filter(|a| a.is_positional() == positional)
+            .map(|a| markdown_arg(a))
+            .collect::<String>();
+
+        if !section.is_empty() {
+            out.push_str(&format!("**{}:**\n\n{}\n", title, section));
+        }
+    }
+
+    for subcommand in command.get_subcommands().filter(|s| !s.is_hide_set()) {
+        if subcommand.get_name() != "help" {
+            markdown_command(subcommand, depth + 1, out);
+        }
+    }
+}
+
+/// Markdown reference of every command and of the template file
+pub fn markdown_reference() -> String {
+    let mut out =
+        String::from("<!-- Generated by `advent-code-cli reference`. Do not edit by hand -->\n\n");
+    markdown_command(&command(), 1, &mut out);
+
+    out.push_str("# Template file\n\n");
+    out.push_str(
+        "Each top-level key of the template file is a language. \
+         It is rendered with [tera](https://keats.github.io/tera/) before it is read.\n\n",
+    );
+    out.push_str(&format!("```yml\n{}```\n\n", TEMPLATE_EXAMPLE));
+
+    for (title, header, rows) in [
+        ("Keys", "Key", TEMPLATE_KEYS),
+        ("Variables", "Variable", TEMPLATE_VARIABLES),
+    ] {
+        out.push_str(&format!(
+            "## {}\n\n| {} | Description |\n| --- | --- |\n",
+            title, header
+        ));
+        for (name, description) in rows {
+            out.push_str(&format!("| `{}` | {} |\n", name, description));
+        }
+        out.push('\n');
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+/// Roff section with the format of the template file
+fn template_man_section() -> String {
+    let mut roff = Roff::new();
+
+    for (title, rows) in [
+        ("TEMPLATE KEYS", TEMPLATE_KEYS),
+        ("TEMPLATE VARIABLES", TEMPLATE_VARIABLES),
+    ] {
+        roff.control("SH", [title]);
+        for (name, description) in rows {
+            roff.control("TP", [])
+                .text([bold(*name)])
+                .text([roman(description.replace('`', ""))]);
+        }
+    }
+
+    roff.to_roff()
+}
+
+/// Man page of the cli, with the template file format, and one per subcommand
+pub fn man_pages() -> io::Result<Vec<(String, Vec<u8>)>> {
+    let command = command();
+
+    let man = Man::new(command.clone());
+    let mut main = vec![];
+    man.render(&mut main)?;
+    main.extend(template_man_section().into_bytes());
+
+    let mut pages = vec![(man.get_filename(), main)];
+
+    for subcommand in command.get_subcommands().filter(|s| !s.is_hide_set()) {
+        if subcommand.get_name() == "help" {
+            continue;
+        }
+
+        let man = Man::new(subcommand.clone());
+        let mut page = vec![];
+        man.render(&mut page)?;
+        pages.push((man.get_filename(), page));
+    }
+
+    Ok(pages)
+}
+
+/// Writes the man pages and the markdown reference. Returns the written files
+pub fn write_reference(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written = vec![];
+
+    let markdown = dir.join(MARKDOWN_FILE);
+    fs::write(&markdown, markdown_reference())?;
+    written.push(markdown);
+
+    let man_dir = dir.join("man");
+    fs::create_dir_all(&man_dir)?;
+    for (file, content) in man_pages()? {
+        let path = man_dir.join(file);
+        fs::write(&path, content)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_reference_is_up_to_date() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("docs")
+            .join(MARKDOWN_FILE);
+        let committed = fs::read_to_string(&path).unwrap_or_default();
+
+        assert!(
+            committed == markdown_reference(),
+            "{} is out of date. Run `cargo run -- reference` to update it",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn test_man_pages() {
+        let pages = man_pages().unwrap();
+
+        let (name, main) = &pages[0];
+        assert_eq!(name, "advent-code-cli.1");
+        let main = String::from_utf8_lossy(main);
+        assert!(main.contains(".SH \"TEMPLATE KEYS\""));
+        assert!(main.contains("init_commands"));
+
+        assert!(pages
+            .iter()
+            .any(|(name, _)| name == "advent-code-cli-add.1"));
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::Error,
+    interfaces::{DayChallenge, Hooks, ProgrammingTemplate},
+    yaml_parser::{
+        global_hooks, list_language_aliases, resolve_language, template_for_challenge,
+        YamlParserError,
+    },
+};
+
+/// Directory of the base directory with extra template files
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// A template file and its content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateSource {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// A language of the registry and where it comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateLanguage {
+    pub name: String,
+    /// Other names of the language
+    pub aliases: Vec<String>,
+    /// File with the definition that is used
+    pub source: PathBuf,
+    /// Files with definitions of the language that were overridden
+    pub overrides: Vec<PathBuf>,
+    /// Aliases that are also the name or an alias of other languages, with
+    /// those languages. The language defined first is used for them
+    pub collisions: Vec<(String, String)>,
+}
+
+/// Every template file in use. When a language is defined in more than one
+/// file, the definition of the last file wins
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRegistry {
+    sources: Vec<TemplateSource>,
+}
+
+impl TemplateRegistry {
+    pub fn new(sources: Vec<TemplateSource>) -> Self {
+        Self { sources }
+    }
+
+    /// Reads the `*.yml` files of the templates directory sorted by name,
+    /// followed by the template files in order, so the files given explicitly
+    /// override the directory
+    pub fn load(template_files: &[PathBuf], templates_dir: &Path) -> Result<Self, Error> {
+        let mut paths = vec![];
+
+        if templates_dir.is_dir() {
+            let mut layered = fs::read_dir(templates_dir)
+                .map_err(|e| Error::io(format!("Unable to read {}", templates_dir.display()), e))?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.is_file()
+                        && p.extension()
+                            .is_some_and(|ext| ext == "yml" || ext == "yaml")
+                })
+                .collect::<Vec<PathBuf>>();
+            layered.sort();
+
+            // Files given explicitly keep their place
+            let given = template_files
+                .iter()
+                .filter_map(|p| p.canonicalize().ok())
+                .collect::<Vec<PathBuf>>();
+            paths.extend(layered.into_iter().filter(|p| {
+                p.canonicalize()
+                    .map_or(true, |canonical| !given.contains(&canonical))
+            }));
+        }
+        paths.extend_from_slice(template_files);
+
+        let sources = paths
+            .into_iter()
+            .map(|path| {
+                let content = fs::read_to_string(&path).map_err(|e| {
+                    Error::io(
+                        format!("Unable to read the template file {}", path.display()),
+                        e,
+                    )
+                })?;
+                Ok(TemplateSource { path, content })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self::new(sources))
+    }
+
+    pub fn sources(&self) -> &[TemplateSource] {
+        &self.sources
+    }
+
+    /// Every language with the file it comes from, in the order they are first defined
+    pub fn languages(&self) -> Result<Vec<TemplateLanguage>, YamlParserError> {
+        let mut languages: Vec<TemplateLanguage> = vec![];
+
+        for source in &self.sources {
+            for (name, aliases) in list_language_aliases(&source.content)? {
+                match languages.iter_mut().find(|l| l.name == name) {
+                    Some(language) => {
+                        let previous = std::mem::replace(&mut language.source, source.path.clone());
+                        language.overrides.push(previous);
+                        language.aliases = aliases;
+                    }
+                    None => languages.push(TemplateLanguage {
+                        name,
+                        aliases,
+                        source: source.path.clone(),
+                        overrides: vec![],
+                        collisions: vec![],
+                    }),
+                }
+            }
+        }
+
+        if languages.is_empty() {
+            return Err(YamlParserError::NoLanguagesProvided);
+        }
+
+        let names = languages
+            .iter()
+            .map(|l| {
+                let mut names = vec![l.name.to_lowercase()];
+                names.extend(l.aliases.iter().map(|a| a.to_lowercase()));
+                (l.name.clone(), names)
+            })
+            .collect::<Vec<_>>();
+        for language in &mut languages {
+            for alias in &language.aliases {
+                let lowercase = alias.to_lowercase();
+                language.collisions.extend(
+                    names
+                        .iter()
+                        .filter(|(name, names)| {
+                            *name != language.name && names.contains(&lowercase)
+                        })
+                        .map(|(name, _)| (alias.clone(), name.clone())),
+                );
+            }
+        }
+
+        Ok(languages)
+    }
+
+    /// Names of every language of the registry
+    pub fn language_names(&self) -> Result<Vec<String>, YamlParserError> {
+        Ok(self.languages()?.into_iter().map(|l| l.name).collect())
+    }
+
+    /// Name of a language given by its name or one of its aliases, ignoring the case
+    pub fn resolve_language(&self, language: &str) -> Result<String, YamlParserError> {
+        let languages = self
+            .languages()?
+            .into_iter()
+            .map(|l| (l.name, l.aliases))
+            .collect::<Vec<_>>();
+
+        resolve_language(language, &languages)
+    }
+
+    /// Template file with the definition of the language that is used
+    pub fn source_of(&self, language: &str) -> Result<&TemplateSource, YamlParserError> {
+        let language = self.resolve_language(language)?;
+
+        for source in self.sources.iter().rev() {
+            if list_language_aliases(&source.content)?
+                .iter()
+                .any(|(name, _)| *name == language)
+            {
+                return Ok(source);
+            }
+        }
+
+        Err(YamlParserError::NoLanguageFound {
+            language,
+            suggestions: vec![],
+        })
+    }
+
+    /// Top-level hooks of every template file, in order
+    pub fn global_hooks(&self, day_challenge: &DayChallenge) -> Result<Hooks, YamlParserError> {
+        let mut hooks = Hooks::default();
+        for source in &self.sources {
+            hooks.extend(global_hooks(&source.content, day_challenge)?);
+        }
+
+        Ok(hooks)
+    }
+
+    /// Renders the template of the language of the challenge. Its hooks run
+    /// after the global ones
+    pub fn template_for_challenge(
+        &self,
+        day_challenge: &DayChallenge,
+    ) -> Result<ProgrammingTemplate, YamlParserError> {
+        let source = self.source_of(&day_challenge.language)?;
+        let mut template = template_for_challenge(&source.content, day_challenge)?;
+
+        let mut hooks = self.global_hooks(day_challenge)?;
+        hooks.extend(std::mem::take(&mut template.hooks));
+        template.hooks = hooks;
+
+        Ok(template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    fn source(path: &str, content: &str) -> TemplateSource {
+        TemplateSource {
+            path: PathBuf::from(path),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_later_files_override_languages() {
+        let registry = TemplateRegistry::new(vec![
+            source(
+                "template.yml",
+                "rust:\n  run: cargo run\npython:\n  run: python main.py\n",
+            ),
+            source(
+                "templates/me.yml",
+                "rust:\n  aliases: [rs]\n  run: cargo run --release\n",
+            ),
+        ]);
+
+        let languages = registry.languages().unwrap();
+        assert_eq!(
+            languages,
+            vec![
+                TemplateLanguage {
+                    name: "rust".into(),
+                    aliases: vec!["rs".into()],
+                    source: "templates/me.yml".into(),
+                    overrides: vec!["template.yml".into()],
+                    collisions: vec![],
+                },
+                TemplateLanguage {
+                    name: "python".into(),
+                    aliases: vec![],
+                    source: "template.yml".into(),
+                    overrides: vec![],
+                    collisions: vec![],
+                },
+            ]
+        );
+
+        let challenge = DayChallenge::new(1, 2022, "rust".into(), "calories".into());
+        let template = registry.template_for_challenge(&challenge).unwrap();
+        assert_eq!(template.run.unwrap().command, "cargo run --release");
+
+        let challenge = DayChallenge::new(1, 2022, "go".into(), "calories".into());
+        assert!(matches!(
+            registry.template_for_challenge(&challenge),
+            Err(YamlParserError::NoLanguageFound { .. })
+        ));
+
+        assert_eq!(registry.resolve_language("RS").unwrap(), "rust");
+        assert_eq!(registry.resolve_language("Python").unwrap(), "python");
+    }
+
+    #[test]
+    fn test_alias_collisions_are_reported() {
+        let registry = TemplateRegistry::new(vec![source(
+            "template.yml",
+            "rust:\n  aliases: [rs]\nrust-nightly:\n  aliases: [RS, nightly]\nc:\n  aliases: [Rust]\n",
+        )]);
+
+        let collisions = registry
+            .languages()
+            .unwrap()
+            .into_iter()
+            .map(|l| (l.name, l.collisions))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            collisions,
+            vec![
+                ("rust".into(), vec![("rs".into(), "rust-nightly".into())]),
+                ("rust-nightly".into(), vec![("RS".into(), "rust".into())]),
+                ("c".into(), vec![("Rust".into(), "rust".into())]),
+            ]
+        );
+        assert_eq!(registry.resolve_language("rs").unwrap(), "rust");
+    }
+
+    #[test]
+    fn test_global_hooks_run_before_the_language_ones() {
+        let registry = TemplateRegistry::new(vec![
+            source(
+                "template.yml",
+                "hooks:\n  post_add: [echo team]\nrust:\n  hooks:\n    post_add: [echo {{ language }}]\n",
+            ),
+            source("templates/me.yml", "hooks:\n  post_add: [echo me]\n"),
+        ]);
+
+        assert_eq!(registry.language_names().unwrap(), vec!["rust"]);
+        assert!(registry.resolve_language("hooks").is_err());
+
+        let challenge = DayChallenge::new(1, 2023, "rust".into(), "title".into());
+        let template = registry.template_for_challenge(&challenge).unwrap();
+        let post_add = template
+            .hooks
+            .post_add
+            .iter()
+            .map(|c| c.command.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(post_add, vec!["echo team", "echo me", "echo rust"]);
+    }
+
+    #[test]
+    fn test_load_templates_directory() {
+        let tmp = TempDir::new("testing_registry").unwrap();
+        let base = tmp.path().join("template.yml");
+        let dir = tmp.path().join(TEMPLATES_DIR);
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(&base, "rust:\n  files:\n    - input.txt\n").unwrap();
+        fs::write(dir.join("30-rust.yml"), "rust:\n  files:\n    - data.txt\n").unwrap();
+        fs::write(dir.join("20-go.yml"), "go:\n  files:\n    - input.txt\n").unwrap();
+        fs::write(
+            dir.join("10-python.yml"),
+            "python:\n  files:\n    - input.txt\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "not a template").unwrap();
+
+        let registry =
+            TemplateRegistry::load(&[base.clone(), dir.join("20-go.yml")], &dir).unwrap();
+
+        let paths = registry
+            .sources()
+            .iter()
+            .map(|s| s.path.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("10-python.yml"),
+                dir.join("30-rust.yml"),
+                base.clone(),
+                dir.join("20-go.yml")
+            ]
+        );
+        assert_eq!(
+            registry.language_names().unwrap(),
+            vec!["python", "rust", "go"]
+        );
+
+        // The files given explicitly override the directory
+        let rust = registry.source_of("rust").unwrap();
+        assert_eq!(rust.path, base);
+    }
+}
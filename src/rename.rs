@@ -0,0 +1,300 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::interfaces::ProgrammingTemplate;
+
+/// New content of a template file that embedded the title of a day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rerender {
+    /// File to write
+    pub path: PathBuf,
+    /// Previous path of the file, when its name embedded the title
+    pub previous_path: Option<PathBuf>,
+    pub old_content: String,
+    pub new_content: String,
+}
+
+impl Rerender {
+    /// Lines removed and added by the rerender
+    pub fn diff(&self) -> String {
+        let mut diff = match &self.previous_path {
+            Some(previous) => format!("--- {}\n+++ {}\n", previous.display(), self.path.display()),
+            None => format!("--- {0}\n+++ {0}\n", self.path.display()),
+        };
+
+        let old_lines = self.old_content.lines().collect::<Vec<&str>>();
+        let new_lines = self.new_content.lines().collect::<Vec<&str>>();
+        for (sign, line) in line_diff(&old_lines, &new_lines) {
+            if sign != ' ' {
+                diff.push_str(&format!("{}{}\n", sign, line));
+            }
+        }
+
+        diff
+    }
+
+    /// Writes the new content, renaming the file if needed
+    pub fn apply(&self) -> io::Result<()> {
+        fs::write(&self.path, &self.new_content)?;
+
+        if let Some(previous) = &self.previous_path {
+            fs::remove_file(previous)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Lines kept (` `), removed (`-`) and added (`+`) to turn `old` into `new`,
+/// from their longest common subsequence
+fn line_diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(char, &'a str)> {
+    // common[i][j] is the length of the common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut changes = vec![];
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            changes.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            changes.push(('-', old[i]));
+            i += 1;
+        } else {
+            changes.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    changes
+}
+
+/// Moves the directory of a day to its new path
+pub fn move_day(from: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::rename(from, to)
+}
+
+/// Blocks of lines of `old` replaced by lines of `new`, in order. Added lines
+/// are attached to the line kept before them, or inserted at the start
+fn changed_blocks<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Vec<&'a str>, Vec<&'a str>)> {
+    let mut blocks = vec![];
+    let mut kept = None;
+    let (mut removed, mut added) = (vec![], vec![]);
+
+    for (sign, line) in line_diff(old, new).into_iter().chain([(' ', "")]) {
+        match sign {
+            '-' => removed.push(line),
+            '+' => added.push(line),
+            _ => {
+                if !removed.is_empty() || !added.is_empty() {
+                    if removed.is_empty() {
+                        if let Some(kept) = kept {
+                            removed.push(kept);
+                            added.insert(0, kept);
+                        }
+                    }
+                    blocks.push((removed, added));
+                    (removed, added) = (vec![], vec![]);
+                }
+                kept = Some(line);
+            }
+        }
+    }
+
+    blocks
+}
+
+/// Replaces the lines of `current` that were rendered with the old title by
+/// the ones rendered with the new title, block by block in order. Edited lines
+/// are kept
+pub fn replace_rendered_lines(current: &str, old_rendered: &str, new_rendered: &str) -> String {
+    let old_lines = old_rendered.lines().collect::<Vec<&str>>();
+    let new_lines = new_rendered.lines().collect::<Vec<&str>>();
+
+    let mut lines = current.lines().collect::<Vec<&str>>();
+    let mut cursor = 0;
+    for (old, new) in changed_blocks(&old_lines, &new_lines) {
+        let start = if old.is_empty() {
+            Some(cursor)
+        } else {
+            (cursor..lines.len()).find(|&i| lines[i..].starts_with(&old))
+        };
+
+        if let Some(start) = start {
+            lines.splice(start..start + old.len(), new.iter().copied());
+            cursor = start + new.len();
+        }
+    }
+
+    let mut content = lines.join("\n");
+    if current.ends_with('\n') {
+        content.push('\n');
+    }
+
+    content
+}
+
+/// Template files of the day whose content or name embedded the old title
+pub fn rerender_files(
+    day_path: &Path,
+    old_template: &ProgrammingTemplate,
+    new_template: &ProgrammingTemplate,
+) -> io::Result<Vec<Rerender>> {
+    let mut rerenders = vec![];
+
+    for (old, new) in old_template.files.iter().zip(&new_template.files) {
+        let old_rendered = old.content.clone().unwrap_or_default();
+        let new_rendered = new.content.clone().unwrap_or_default();
+        if old.name == new.name && old_rendered == new_rendered {
+            continue;
+        }
+
+        let previous = day_path.join(&old.name);
+        if !previous.is_file() {
+            continue;
+        }
+
+        let old_content = fs::read_to_string(&previous)?;
+        let new_content = replace_rendered_lines(&old_content, &old_rendered, &new_rendered);
+        let renamed = old.name != new.name;
+
+        if renamed || old_content != new_content {
+            rerenders.push(Rerender {
+                path: day_path.join(&new.name),
+                previous_path: renamed.then_some(previous),
+                old_content,
+                new_content,
+            });
+        }
+    }
+
+    Ok(rerenders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    use crate::interfaces::File;
+
+    #[test]
+    fn test_replace_rendered_lines_keeps_edits() {
+        let old_rendered = "# Day 1: wrong\n\nTODO\n";
+        let new_rendered = "# Day 1: right\n\nTODO\n";
+        let current = "# Day 1: wrong\n\nSolved with a heap\n";
+
+        assert_eq!(
+            replace_rendered_lines(current, old_rendered, new_rendered),
+            "# Day 1: right\n\nSolved with a heap\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_rendered_lines_with_added_lines() {
+        assert_eq!(
+            replace_rendered_lines("# A\nB\nB\n", "# A\nB", "# A\nX\nB"),
+            "# A\nX\nB\nB\n"
+        );
+
+        let old_rendered = "# wrong\nB\n";
+        let new_rendered = "# right\nA longer title\nB\n";
+        assert_eq!(
+            replace_rendered_lines("# wrong\nB\nnotes\nB\n", old_rendered, new_rendered),
+            "# right\nA longer title\nB\nnotes\nB\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_shows_added_lines() {
+        let rerender = Rerender {
+            path: PathBuf::from("README.md"),
+            previous_path: None,
+            old_content: "# wrong\nnotes\n".into(),
+            new_content: "# right\n\nA longer title\nnotes\nend\n".into(),
+        };
+
+        assert_eq!(
+            rerender.diff(),
+            "--- README.md\n+++ README.md\n-# wrong\n+# right\n+\n+A longer title\n+end\n"
+        );
+    }
+
+    #[test]
+    fn test_rerender_files() {
+        let tmp = TempDir::new("testing_rename").unwrap();
+        let day = tmp.path();
+        fs::write(day.join("README.md"), "# wrong\nnotes\n").unwrap();
+        fs::write(day.join("wrong.txt"), "").unwrap();
+        fs::write(day.join("input.txt"), "1 2 3").unwrap();
+
+        let template = |title: &str| ProgrammingTemplate {
+            files: vec![
+                File::new("README.md", &format!("# {}\n", title)),
+                File::empty(&format!("{}.txt", title)),
+                File::empty("input.txt"),
+            ],
+            ..Default::default()
+        };
+
+        let rerenders = rerender_files(day, &template("wrong"), &template("right")).unwrap();
+
+        assert_eq!(rerenders.len(), 2);
+        assert_eq!(rerenders[0].new_content, "# right\nnotes\n");
+        assert_eq!(
+            rerenders[0].diff(),
+            format!(
+                "--- {0}\n+++ {0}\n-# wrong\n+# right\n",
+                day.join("README.md").display()
+            )
+        );
+
+        for rerender in &rerenders {
+            rerender.apply().unwrap();
+        }
+        assert!(day.join("right.txt").exists());
+        assert!(!day.join("wrong.txt").exists());
+        assert_eq!(fs::read_to_string(day.join("input.txt")).unwrap(), "1 2 3");
+    }
+
+    #[test]
+    fn test_move_day_does_not_overwrite() {
+        let tmp = TempDir::new("testing_rename").unwrap();
+        let from = tmp.path().join("Day-01-wrong");
+        let to = tmp.path().join("Day-01-right");
+        fs::create_dir_all(&from).unwrap();
+
+        move_day(&from, &to).unwrap();
+        assert!(to.exists() && !from.exists());
+
+        fs::create_dir_all(&from).unwrap();
+        assert_eq!(
+            move_day(&from, &to).unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+    }
+}
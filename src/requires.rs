@@ -0,0 +1,215 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    process::Command,
+    str::FromStr,
+};
+
+use regex::Regex;
+
+use crate::utils::find_executable;
+
+/// Version of a tool, like `1.70.0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version(pub Vec<u64>);
+
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .map(Version)
+            .map_err(|_| format!("{:?} is not a version like 1.70", s))
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let parts = self.0.iter().map(u64::to_string).collect::<Vec<String>>();
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    /// Missing parts count as zero, so `1.70` is the same as `1.70.0`
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.0.len().max(other.0.len());
+        let part = |v: &Version, i: usize| v.0.get(i).copied().unwrap_or(0);
+
+        (0..len)
+            .map(|i| part(self, i).cmp(&part(other, i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// A tool a language needs, with its minimum version: `cargo` or `rustc>=1.70`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    pub tool: String,
+    pub min_version: Option<Version>,
+}
+
+impl FromStr for Requirement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tool, min_version) = match s.split_once(">=") {
+            Some((tool, version)) => (tool.trim(), Some(version.parse()?)),
+            None => (s.trim(), None),
+        };
+
+        if tool.is_empty() || tool.contains(char::is_whitespace) {
+            return Err(format!("{:?} is not a tool like cargo or rustc>=1.70", s));
+        }
+
+        Ok(Self {
+            tool: tool.to_string(),
+            min_version,
+        })
+    }
+}
+
+impl Display for Requirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.min_version {
+            Some(version) => write!(f, "{}>={}", self.tool, version),
+            None => write!(f, "{}", self.tool),
+        }
+    }
+}
+
+/// Why a requirement is not met
+#[derive(Debug, PartialEq, Eq)]
+pub enum RequirementError {
+    /// The tool is not on the `PATH`
+    NotFound(String),
+    /// The version of the tool is older than the required one
+    Outdated {
+        tool: String,
+        found: Version,
+        required: Version,
+    },
+    /// The version could not be read from `<tool> --version`
+    UnknownVersion(String),
+}
+
+impl Display for RequirementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RequirementError::NotFound(tool) => write!(f, "{} is not on the PATH", tool),
+            RequirementError::Outdated {
+                tool,
+                found,
+                required,
+            } => write!(f, "{} {} is older than {}", tool, found, required),
+            RequirementError::UnknownVersion(tool) => {
+                write!(f, "Unable to read the version of {}", tool)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RequirementError {}
+
+/// First version number in the output of `--version`
+pub fn parse_version(output: &str) -> Option<Version> {
+    let re = Regex::new(r"\d+(\.\d+)+").expect("The version regex is valid");
+    re.find(output)?.as_str().parse().ok()
+}
+
+/// Checks that the tool is on the `PATH` and, when needed, that
+/// `<tool> --version` is recent enough
+pub fn check_requirement(requirement: &Requirement) -> Result<(), RequirementError> {
+    let Some(path) = find_executable(&requirement.tool) else {
+        return Err(RequirementError::NotFound(requirement.tool.clone()));
+    };
+
+    let Some(required) = &requirement.min_version else {
+        return Ok(());
+    };
+
+    // Some tools print their version to stderr
+    let found = Command::new(path)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| {
+            parse_version(&String::from_utf8_lossy(&output.stdout))
+                .or_else(|| parse_version(&String::from_utf8_lossy(&output.stderr)))
+        })
+        .ok_or_else(|| RequirementError::UnknownVersion(requirement.tool.clone()))?;
+
+    if &found < required {
+        return Err(RequirementError::Outdated {
+            tool: requirement.tool.clone(),
+            found,
+            required: required.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks every requirement, returning the ones that are not met
+pub fn check_requirements(requirements: &[Requirement]) -> Vec<RequirementError> {
+    requirements
+        .iter()
+        .filter_map(|r| check_requirement(r).err())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requirements() {
+        assert_eq!(
+            "rustc>=1.70".parse::<Requirement>().unwrap(),
+            Requirement {
+                tool: "rustc".into(),
+                min_version: Some(Version(vec![1, 70])),
+            }
+        );
+        assert_eq!("cargo".parse::<Requirement>().unwrap().min_version, None);
+        assert!("rustc>=one".parse::<Requirement>().is_err());
+        assert!(">=1.0".parse::<Requirement>().is_err());
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        let version = |v: &str| v.parse::<Version>().unwrap();
+
+        assert!(version("1.70.0") >= version("1.70"));
+        assert!(version("1.9") < version("1.10"));
+        assert!(version("2") > version("1.99.1"));
+        assert_eq!(
+            parse_version("rustc 1.74.1 (a28077b28 2023-12-04)"),
+            Some(version("1.74.1"))
+        );
+        assert_eq!(parse_version("Python 3.12.0"), Some(version("3.12.0")));
+    }
+
+    #[test]
+    fn test_check_requirements() {
+        let requirements =
+            ["sh", "advent-code-cli-missing-tool"].map(|r| r.parse::<Requirement>().unwrap());
+
+        assert_eq!(
+            check_requirements(&requirements),
+            vec![RequirementError::NotFound(
+                "advent-code-cli-missing-tool".into()
+            )]
+        );
+    }
+}
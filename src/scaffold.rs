@@ -0,0 +1,211 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+    str::FromStr,
+    sync::Mutex,
+    thread,
+};
+
+use crate::{
+    file_handler::AventStructure, index::DayEntry, interfaces::DayChallenge,
+    registry::TemplateRegistry, wizard::LAST_DAY,
+};
+
+/// Title of the days created before their puzzle is known
+pub const PLACEHOLDER_TITLE: &str = "untitled";
+
+/// Days given as `1..=25`, `1..26`, `1-5` or single days, separated by commas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRange(pub Vec<u8>);
+
+impl FromStr for DayRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| {
+            day.trim()
+                .parse::<u8>()
+                .map_err(|_| format!("{:?} is not a day", day.trim()))
+        };
+
+        let mut days = vec![];
+        for part in s.split(',') {
+            let (first, last) = if let Some((a, b)) = part.split_once("..=") {
+                (parse_day(a)?, parse_day(b)?)
+            } else if let Some((a, b)) = part.split_once("..") {
+                (parse_day(a)?, parse_day(b)?.saturating_sub(1))
+            } else if let Some((a, b)) = part.split_once('-') {
+                (parse_day(a)?, parse_day(b)?)
+            } else {
+                let day = parse_day(part)?;
+                (day, day)
+            };
+
+            if first == 0 || last > LAST_DAY || first > last {
+                return Err(format!(
+                    "{:?} is not a range of days between 1 and {}",
+                    part.trim(),
+                    LAST_DAY
+                ));
+            }
+            days.extend(first..=last);
+        }
+
+        days.sort();
+        days.dedup();
+
+        Ok(Self(days))
+    }
+}
+
+/// What happened to each day of a scaffold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScaffoldOutcome {
+    Created(PathBuf),
+    /// The day already exists
+    Skipped(PathBuf),
+    Failed(String),
+}
+
+impl Display for ScaffoldOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldOutcome::Created(path) => write!(f, "created {}", path.display()),
+            ScaffoldOutcome::Skipped(path) => write!(f, "skipped, {} exists", path.display()),
+            ScaffoldOutcome::Failed(e) => write!(f, "failed: {}", e),
+        }
+    }
+}
+
+fn scaffold_day(
+    structure: &AventStructure,
+    registry: &TemplateRegistry,
+    challenge: &DayChallenge,
+) -> ScaffoldOutcome {
+    let template = match registry.template_for_challenge(challenge) {
+        Ok(template) => template,
+        Err(e) => return ScaffoldOutcome::Failed(e.to_string()),
+    };
+
+    match structure.add_day(challenge, &template) {
+        Ok(path) => ScaffoldOutcome::Created(path),
+        Err(e) => ScaffoldOutcome::Failed(e.to_string()),
+    }
+}
+
+/// Creates the days of the challenges that do not exist yet, running up to
+/// `jobs` days at the same time. Results are sorted by day
+pub fn scaffold_days(
+    structure: &AventStructure,
+    registry: &TemplateRegistry,
+    challenges: Vec<DayChallenge>,
+    existing: &[DayEntry],
+    jobs: usize,
+) -> Vec<(DayChallenge, ScaffoldOutcome)> {
+    let mut results = vec![];
+    let mut pending = vec![];
+
+    for challenge in challenges {
+        let found = existing.iter().find(|d| {
+            d.year == challenge.year
+                && d.language.eq_ignore_ascii_case(&challenge.language)
+                && d.day == challenge.day
+        });
+
+        match found {
+            Some(day) => {
+                let outcome = ScaffoldOutcome::Skipped(day.path.clone());
+                results.push((challenge, outcome));
+            }
+            None => pending.push(challenge),
+        }
+    }
+
+    let queue = Mutex::new(pending.into_iter());
+    let results = Mutex::new(results);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                let Some(challenge) = queue.lock().unwrap().next() else {
+                    break;
+                };
+
+                let outcome = scaffold_day(structure, registry, &challenge);
+                results.lock().unwrap().push((challenge, outcome));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(challenge, _)| challenge.day);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    use crate::{index::scan_days, registry::TemplateSource};
+
+    #[test]
+    fn test_parse_day_range() {
+        assert_eq!(
+            "1..=25".parse::<DayRange>().unwrap().0,
+            (1..=25).collect::<Vec<u8>>()
+        );
+        assert_eq!("1..4".parse::<DayRange>().unwrap().0, vec![1, 2, 3]);
+        assert_eq!("3-5, 1,4".parse::<DayRange>().unwrap().0, vec![1, 3, 4, 5]);
+        assert!("0..=3".parse::<DayRange>().is_err());
+        assert!("20..=26".parse::<DayRange>().is_err());
+        assert!("5-2".parse::<DayRange>().is_err());
+        assert!("one".parse::<DayRange>().is_err());
+    }
+
+    #[test]
+    fn test_scaffold_days() {
+        let tmp = TempDir::new("testing_scaffold").unwrap();
+        let structure = AventStructure::new(tmp.path().to_path_buf());
+        let registry = TemplateRegistry::new(vec![TemplateSource {
+            path: "template.yml".into(),
+            content:
+                "rust:\n  init_commands:\n    - test {{ day }} -ne 3\n  files:\n    - input.txt\n"
+                    .into(),
+        }]);
+
+        let challenge = |day| DayChallenge::new(day, 2023, "rust".into(), PLACEHOLDER_TITLE.into());
+        structure
+            .add_day(
+                &challenge(2),
+                &registry.template_for_challenge(&challenge(2)).unwrap(),
+            )
+            .unwrap();
+        let existing = scan_days(&structure.base_directory, &structure.layout).unwrap();
+
+        let results = scaffold_days(
+            &structure,
+            &registry,
+            (1..=4).map(challenge).collect(),
+            &existing,
+            2,
+        );
+
+        let outcomes = results
+            .into_iter()
+            .map(|(c, outcome)| (c.day, outcome))
+            .collect::<Vec<_>>();
+        let day_path = |day| {
+            structure
+                .base_directory
+                .join(structure.layout.day_path(&challenge(day)))
+        };
+
+        assert_eq!(outcomes[0], (1, ScaffoldOutcome::Created(day_path(1))));
+        assert_eq!(outcomes[1], (2, ScaffoldOutcome::Skipped(day_path(2))));
+        assert!(matches!(outcomes[2], (3, ScaffoldOutcome::Failed(_))));
+        assert_eq!(outcomes[3], (4, ScaffoldOutcome::Created(day_path(4))));
+        assert!(day_path(4).join("input.txt").exists());
+    }
+}
@@ -0,0 +1,256 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::REPOSITORY_CONFIG_FILE,
+    yaml_parser::{list_language_aliases, resolve_language},
+};
+
+/// A template of a language embedded in the binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Starter {
+    /// Language defined by the template
+    pub name: &'static str,
+    pub template: &'static str,
+}
+
+/// Every starter template shipped with the cli
+pub const STARTERS: &[Starter] = &[
+    Starter {
+        name: "rust",
+        template: include_str!("../starters/rust.yml"),
+    },
+    Starter {
+        name: "python",
+        template: include_str!("../starters/python.yml"),
+    },
+    Starter {
+        name: "go",
+        template: include_str!("../starters/go.yml"),
+    },
+    Starter {
+        name: "typescript",
+        template: include_str!("../starters/typescript.yml"),
+    },
+    Starter {
+        name: "c++",
+        template: include_str!("../starters/cpp.yml"),
+    },
+    Starter {
+        name: "haskell",
+        template: include_str!("../starters/haskell.yml"),
+    },
+];
+
+/// Lines of the `.gitignore` written by `init`. Puzzle inputs must not be published
+pub const GITIGNORE_ENTRIES: &[&str] = &[
+    "input.txt",
+    "target/",
+    "__pycache__/",
+    "node_modules/",
+    "solution",
+];
+
+/// Finds a starter by its name or one of its aliases, ignoring the case
+pub fn find_starter(name: &str) -> Option<&'static Starter> {
+    let languages = STARTERS
+        .iter()
+        .flat_map(|s| list_language_aliases(s.template).unwrap_or_default())
+        .collect::<Vec<_>>();
+    let name = resolve_language(name, &languages).ok()?;
+
+    STARTERS.iter().find(|s| s.name == name)
+}
+
+/// Template file with every given starter
+pub fn starter_template(starters: &[&Starter]) -> String {
+    starters
+        .iter()
+        .map(|s| s.template.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n\n")
+        + "\n"
+}
+
+/// What `init` did with each path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitStep {
+    Created(PathBuf),
+    Updated(PathBuf),
+    /// The file already exists and was left untouched
+    Skipped(PathBuf),
+}
+
+/// Writes a file unless it exists. With `force` it is overwritten
+fn write_new_file(path: PathBuf, content: &str, force: bool) -> io::Result<InitStep> {
+    if path.exists() && !force {
+        return Ok(InitStep::Skipped(path));
+    }
+
+    let existed = path.exists();
+    fs::write(&path, content)?;
+
+    Ok(if existed {
+        InitStep::Updated(path)
+    } else {
+        InitStep::Created(path)
+    })
+}
+
+/// Adds the missing entries to the `.gitignore`, keeping the existing ones
+fn update_gitignore(path: PathBuf) -> io::Result<InitStep> {
+    let existed = path.exists();
+    let mut content = if existed {
+        fs::read_to_string(&path)?
+    } else {
+        String::new()
+    };
+
+    let missing = GITIGNORE_ENTRIES
+        .iter()
+        .filter(|entry| !content.lines().any(|l| l.trim() == **entry))
+        .collect::<Vec<_>>();
+
+    if missing.is_empty() {
+        return Ok(InitStep::Skipped(path));
+    }
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !existed {
+        content.push_str("# Puzzle inputs must not be published\n");
+    }
+    for entry in missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
+
+    fs::write(&path, content)?;
+
+    Ok(if existed {
+        InitStep::Updated(path)
+    } else {
+        InitStep::Created(path)
+    })
+}
+
+/// Bootstraps a repository in `base`: the `src/` directory of the days, a
+/// `template.yml` with the starters, a `.gitignore` and a `.advent.toml`
+pub fn init_repository(
+    base: &Path,
+    starters: &[&Starter],
+    force: bool,
+) -> io::Result<Vec<InitStep>> {
+    let mut steps = vec![];
+
+    // Directory of the days, see `AventStructure::new`
+    let src = base.join("src");
+    if src.is_dir() {
+        steps.push(InitStep::Skipped(src));
+    } else {
+        fs::create_dir_all(&src)?;
+        steps.push(InitStep::Created(src));
+    }
+
+    steps.push(write_new_file(
+        base.join("template.yml"),
+        &starter_template(starters),
+        force,
+    )?);
+
+    steps.push(update_gitignore(base.join(".gitignore"))?);
+
+    let mut config = String::from("# Defaults of advent-code-cli\n");
+    if let Some(starter) = starters.first() {
+        config.push_str(&format!("language = \"{}\"\n", starter.name));
+    }
+    steps.push(write_new_file(
+        base.join(REPOSITORY_CONFIG_FILE),
+        &config,
+        force,
+    )?);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    use crate::{config::Config, interfaces::DayChallenge, yaml_parser::template_for_challenge};
+
+    #[test]
+    fn test_starters_are_valid_templates() {
+        for starter in STARTERS {
+            let challenge = DayChallenge::new(1, 2023, starter.name.into(), "trebuchet".into());
+            let template = template_for_challenge(starter.template, &challenge)
+                .unwrap_or_else(|e| panic!("Invalid starter {}: {}", starter.name, e));
+
+            assert_eq!(template.language, starter.name);
+            assert!(
+                template.run.is_some(),
+                "{} has no run command",
+                starter.name
+            );
+            assert!(
+                template.test.is_some(),
+                "{} has no test command",
+                starter.name
+            );
+            assert!(
+                template.files.iter().any(|f| f.name == "input.txt"),
+                "{} has no input file",
+                starter.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_starter_by_alias() {
+        assert_eq!(find_starter("CPP").unwrap().name, "c++");
+        assert_eq!(find_starter("Rust").unwrap().name, "rust");
+        assert!(find_starter("cobol").is_none());
+    }
+
+    #[test]
+    fn test_init_repository() {
+        let tmp = TempDir::new("testing_init").unwrap();
+        let base = tmp.path();
+        fs::write(base.join(".gitignore"), "target/\n.env").unwrap();
+
+        let starters = [find_starter("python").unwrap(), find_starter("go").unwrap()];
+        let steps = init_repository(base, &starters, false).unwrap();
+
+        assert_eq!(
+            steps,
+            vec![
+                InitStep::Created(base.join("src")),
+                InitStep::Created(base.join("template.yml")),
+                InitStep::Updated(base.join(".gitignore")),
+                InitStep::Created(base.join(REPOSITORY_CONFIG_FILE)),
+            ]
+        );
+
+        let template = fs::read_to_string(base.join("template.yml")).unwrap();
+        assert_eq!(
+            crate::yaml_parser::list_languages(&template).unwrap(),
+            vec!["python", "go"]
+        );
+
+        let gitignore = fs::read_to_string(base.join(".gitignore")).unwrap();
+        assert!(gitignore.starts_with("target/\n.env\ninput.txt\n"));
+        assert_eq!(gitignore.matches("target/").count(), 1);
+
+        let config = Config::load(&base.join(REPOSITORY_CONFIG_FILE)).unwrap();
+        assert_eq!(config.language.as_deref(), Some("python"));
+
+        // Running it again leaves everything untouched
+        let steps = init_repository(base, &starters, false).unwrap();
+        assert!(steps.iter().all(|s| matches!(s, InitStep::Skipped(_))));
+    }
+}
@@ -0,0 +1,323 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+    str::FromStr,
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    index::DayEntry,
+    utils::{civil_from_days, days_from_civil, unix_now, unlock_time},
+};
+
+/// File inside a day directory with when the day was created and solved
+pub const TIMES_FILE: &str = "times.yml";
+
+/// Error type for the times of a day
+#[derive(Debug)]
+pub enum TimesError {
+    IoError(io::Error),
+    YamlError(serde_yaml::Error),
+}
+
+impl Display for TimesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TimesError::IoError(_) => write!(f, "Unable to access the {} file", TIMES_FILE),
+            TimesError::YamlError(_) => write!(f, "The {} file is not valid", TIMES_FILE),
+        }
+    }
+}
+
+impl std::error::Error for TimesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TimesError::IoError(e) => Some(e),
+            TimesError::YamlError(e) => Some(e),
+        }
+    }
+}
+
+/// Seconds since the unix epoch, written in UTC like `2023-12-01T05:00:00Z`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    pub fn now() -> Self {
+        Self(unix_now())
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.0.div_euclid(86_400));
+        let seconds = self.0.rem_euclid(86_400);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        )
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("{:?} is not a time like 2023-12-01T05:00:00Z", s);
+
+        let (date, time) = s
+            .trim()
+            .strip_suffix('Z')
+            .and_then(|s| s.split_once('T'))
+            .ok_or_else(error)?;
+        let numbers = |part: &str, separator| {
+            part.split(separator)
+                .map(|n| n.parse::<i64>())
+                .collect::<Result<Vec<i64>, _>>()
+                .map_err(|_| error())
+        };
+
+        let (date, time) = (numbers(date, '-')?, numbers(time, ':')?);
+        let (&[year, month, day], &[hours, minutes, seconds]) = (date.as_slice(), time.as_slice())
+        else {
+            return Err(error());
+        };
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(error());
+        }
+
+        let days = days_from_civil(year, month as u32, day as u32);
+        Ok(Self(days * 86_400 + hours * 3600 + minutes * 60 + seconds))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// When a part was first run and when its answer was recorded
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PartTimes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_run: Option<Timestamp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solved: Option<Timestamp>,
+}
+
+impl PartTimes {
+    fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Times of a day. Only the first time of each event is kept
+///
+/// ```yml
+/// created: 2023-12-01T05:00:12Z
+/// part1:
+///   first_run: 2023-12-01T05:06:40Z
+///   solved: 2023-12-01T05:07:02Z
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DayTimes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<Timestamp>,
+    #[serde(skip_serializing_if = "PartTimes::is_empty")]
+    pub part1: PartTimes,
+    #[serde(skip_serializing_if = "PartTimes::is_empty")]
+    pub part2: PartTimes,
+}
+
+impl DayTimes {
+    /// Reads the times of the day directory. A missing file has no times
+    pub fn load(day_path: &Path) -> Result<Self, TimesError> {
+        let path = day_path.join(TIMES_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(TimesError::IoError)?;
+        serde_yaml::from_str::<Option<Self>>(&content)
+            .map(Option::unwrap_or_default)
+            .map_err(TimesError::YamlError)
+    }
+
+    /// Writes the times into the day directory
+    pub fn save(&self, day_path: &Path) -> Result<(), TimesError> {
+        let content = serde_yaml::to_string(self).map_err(TimesError::YamlError)?;
+        fs::write(day_path.join(TIMES_FILE), content).map_err(TimesError::IoError)
+    }
+
+    /// Applies the changes to the times of the day and saves them when they
+    /// change. Days without times are only tracked with `create`
+    pub fn update(
+        day_path: &Path,
+        create: bool,
+        change: impl FnOnce(&mut DayTimes),
+    ) -> Result<(), TimesError> {
+        if !create && !day_path.join(TIMES_FILE).exists() {
+            return Ok(());
+        }
+
+        let times = Self::load(day_path)?;
+        let mut updated = times.clone();
+        change(&mut updated);
+
+        if updated != times {
+            updated.save(day_path)?;
+        }
+        Ok(())
+    }
+
+    /// Times of the part (1 or 2)
+    pub fn part_mut(&mut self, part: u8) -> &mut PartTimes {
+        match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        }
+    }
+
+    /// Keeps the first run of the parts whose answer was found
+    pub fn record_runs(&mut self, found: &[Option<String>; 2], at: Timestamp) {
+        for (part, answer) in (1..=2).zip(found) {
+            if answer.is_some() {
+                self.part_mut(part).first_run.get_or_insert(at);
+            }
+        }
+    }
+
+    /// Keeps when the answer of the part was first recorded
+    pub fn record_solved(&mut self, part: u8, at: Timestamp) {
+        let times = self.part_mut(part);
+        times.solved.get_or_insert(at);
+        // Answers recorded by hand count as a run too
+        times.first_run.get_or_insert(at);
+    }
+}
+
+/// Seconds since the unlock of the puzzle like `1:02:03`, or `-` when unknown.
+/// Times before the unlock are negative
+pub fn format_elapsed(seconds: Option<i64>) -> String {
+    let Some(seconds) = seconds else {
+        return "-".into();
+    };
+
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    format!(
+        "{}{}:{:02}:{:02}",
+        sign,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Time since the unlock of the puzzle when the day was created and when each
+/// part was solved
+pub fn since_unlock(entry: &DayEntry, times: &DayTimes) -> [Option<i64>; 3] {
+    let unlock = unlock_time(entry.year, entry.day);
+    let elapsed = |t: Option<Timestamp>| t.map(|t| t.0 - unlock);
+
+    [
+        elapsed(times.created),
+        elapsed(times.part1.solved),
+        elapsed(times.part2.solved),
+    ]
+}
+
+/// Prints a table with the time to solve each day
+pub fn print_times(results: &[(DayEntry, DayTimes)]) {
+    println!(
+        "{:<6} {:<12} {:<4} {:<12} {:<12} {:<12}",
+        "Year", "Language", "Day", "Created", "Part 1", "Part 2"
+    );
+
+    for (entry, times) in results {
+        let [created, part1, part2] = since_unlock(entry, times).map(format_elapsed);
+        println!(
+            "{:<6} {:<12} {:<4} {:<12} {:<12} {:<12}",
+            entry.year,
+            entry.language,
+            format!("{:02}", entry.day),
+            created,
+            part1,
+            part2
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_timestamps() {
+        let unlock = Timestamp(unlock_time(2023, 1));
+
+        assert_eq!(unlock.to_string(), "2023-12-01T05:00:00Z");
+        assert_eq!("2023-12-01T05:00:00Z".parse(), Ok(unlock));
+        assert!("2023-12-01 05:00:00".parse::<Timestamp>().is_err());
+        assert!("2023-13-01T05:00:00Z".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    fn test_only_the_first_times_are_kept() {
+        let tmp = TempDir::new("testing_times").unwrap();
+        let day = tmp.path();
+        let unlock = unlock_time(2023, 1);
+
+        // Days are only tracked once they have times
+        DayTimes::update(day, false, |t| t.record_solved(1, Timestamp(unlock))).unwrap();
+        assert!(!day.join(TIMES_FILE).exists());
+
+        DayTimes::update(day, true, |t| t.created = Some(Timestamp(unlock + 12))).unwrap();
+        let runs = [Some("142".to_string()), None];
+        for at in [unlock + 400, unlock + 500] {
+            DayTimes::update(day, false, |t| t.record_runs(&runs, Timestamp(at))).unwrap();
+        }
+        DayTimes::update(day, false, |t| t.record_solved(1, Timestamp(unlock + 422))).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(day.join(TIMES_FILE)).unwrap(),
+            "created: 2023-12-01T05:00:12Z\n\
+             part1:\n  first_run: 2023-12-01T05:06:40Z\n  solved: 2023-12-01T05:07:02Z\n"
+        );
+
+        let entry = DayEntry {
+            day: 1,
+            year: 2023,
+            language: "rust".into(),
+            title: "Trebuchet".into(),
+            path: day.to_path_buf(),
+        };
+        let times = DayTimes::load(day).unwrap();
+        assert_eq!(
+            since_unlock(&entry, &times).map(format_elapsed),
+            ["0:00:12", "0:07:02", "-"]
+        );
+        assert_eq!(format_elapsed(Some(-90_061)), "-25:01:01");
+    }
+}
@@ -1,49 +1,224 @@
-use dialoguer::Confirm;
-use std::path::PathBuf;
-
-/// Lists the folders in the directory
-/// Only outputs the last of their name
-pub fn list_folder_names(path: &PathBuf) {
-    for entry in std::fs::read_dir(&path).unwrap() {
-        let entry = entry.unwrap();
-        let path = entry.path();
-        if path.is_dir() {
-            println!(
-                "- {}",
-                path.components()
-                    .last()
-                    .unwrap()
-                    .as_os_str()
-                    .to_str()
-                    .unwrap()
-            );
-        }
+use dialoguer::{Confirm, Input};
+use std::{
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    process::Output,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Offset of the timezone of the puzzle releases (EST) from UTC, in seconds
+pub const AOC_UTC_OFFSET: i64 = -5 * 60 * 60;
+
+/// Runs a command with `sh -c` inside a directory and captures its output
+pub fn run_shell_command(command: &str, dir: &Path) -> io::Result<Output> {
+    run_shell_command_with_env(command, dir, &[])
+}
+
+/// Runs a command with `sh -c` inside a directory, with extra environment
+/// variables, and captures its output
+pub fn run_shell_command_with_env(
+    command: &str,
+    dir: &Path,
+    env: &[(&str, String)],
+) -> io::Result<Output> {
+    std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .output()
+}
+
+/// Converts days since the unix epoch into a (year, month, day) date
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Converts a (year, month, day) date into days since the unix epoch
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+/// Seconds since the unix epoch
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Today's date in the timezone of the puzzle releases
+pub fn aoc_today() -> (i64, u32, u32) {
+    civil_from_days((unix_now() + AOC_UTC_OFFSET).div_euclid(86_400))
+}
+
+/// Seconds since the unix epoch when the puzzle of the day unlocks, at
+/// midnight in the timezone of the puzzle releases
+pub fn unlock_time(year: u16, day: u8) -> i64 {
+    days_from_civil(i64::from(year), 12, u32::from(day)) * 86_400 - AOC_UTC_OFFSET
+}
+
+/// Year of the current advent: this year in December, otherwise the last one
+pub fn season_for_date(year: i64, month: u32) -> u16 {
+    if month == 12 {
+        year as u16
+    } else {
+        (year - 1) as u16
+    }
+}
+
+/// Prompts user for the title of a challenge
+pub fn prompt_for_title(day: u8, year: u16) -> io::Result<String> {
+    if !io::stdin().is_terminal() {
+        return Err(io::Error::other(
+            "the title must be given when not running in a terminal",
+        ));
     }
-    return;
+
+    Input::<String>::new()
+        .with_prompt(format!("Title of day {} of {}", day, year))
+        .validate_with(|title: &String| {
+            if title.trim().is_empty() {
+                Err("The title cannot be empty")
+            } else {
+                Ok(())
+            }
+        })
+        .interact_text()
+        .map(|title| title.trim().to_string())
 }
 
-/// Prompts user to remove a directory
-/// If force is true, it will remove it without prompting
-pub fn prompt_to_remove_directory(path: &PathBuf, force: bool) {
+/// Prompts user to remove the directories
+/// If force is true, it will not prompt
+/// Returns whether the directories should be removed
+pub fn prompt_to_remove_directories(paths: &[PathBuf], force: bool) -> io::Result<bool> {
     if force {
-        std::fs::remove_dir_all(path).unwrap();
-        println!("Removed {}", path.display());
-        return;
-    }
-
-    let confirmed = Confirm::new()
-        .with_prompt(format!(
-            "Are you sure you want to delete {}?",
-            path.display()
-        ))
-        .interact()
-        .expect("Canceling operation");
-
-    if confirmed {
-        // Proceed with the desired action
-        std::fs::remove_dir_all(path).unwrap();
-        println!("Removed {}", path.display());
-    } else {
+        return Ok(true);
+    }
+
+    let prompt = match paths {
+        [path] => format!("Are you sure you want to delete {}?", path.display()),
+        _ => {
+            for path in paths {
+                println!("  {}", path.display());
+            }
+            format!(
+                "Are you sure you want to delete these {} directories?",
+                paths.len()
+            )
+        }
+    };
+
+    let confirmed = Confirm::new().with_prompt(prompt).interact()?;
+    if !confirmed {
         println!("Canceling operation");
     }
+
+    Ok(confirmed)
+}
+
+/// Number of single character edits needed to turn `a` into `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Finds an executable in the directories of the `PATH`. Names with a
+/// directory are checked as they are
+pub fn find_executable(name: &str) -> Option<PathBuf> {
+    let is_executable = |path: &Path| {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            path.metadata()
+                .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        }
+        #[cfg(not(unix))]
+        {
+            path.is_file() || path.with_extension("exe").is_file()
+        }
+    };
+
+    if name.contains('/') {
+        let path = PathBuf::from(name);
+        return is_executable(&path).then_some(path);
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(name))
+            .find(|path| is_executable(path))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_327), (2022, 12, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+
+        for days in [0, 19_327, 19_782, -1] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_time(2023, 1), 1_701_406_800);
+    }
+
+    #[test]
+    fn test_season_for_date() {
+        assert_eq!(season_for_date(2023, 12), 2023);
+        assert_eq!(season_for_date(2024, 11), 2023);
+        assert_eq!(season_for_date(2024, 1), 2023);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("python", "python"), 0);
+        assert_eq!(edit_distance("pyton", "python"), 1);
+        assert_eq!(edit_distance("rsut", "rust"), 2);
+        assert_eq!(edit_distance("", "go"), 2);
+    }
+
+    #[test]
+    fn test_find_executable() {
+        assert!(find_executable("sh").is_some());
+        assert!(find_executable("advent-code-cli-missing-tool").is_none());
+    }
 }
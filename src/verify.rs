@@ -0,0 +1,262 @@
+use regex::Regex;
+
+use crate::{
+    answers::Answers,
+    index::DayEntry,
+    interfaces::AnswerPatterns,
+    registry::TemplateRegistry,
+    times::{DayTimes, Timestamp},
+    utils::run_shell_command,
+};
+
+/// Result of comparing one part of a day against its recorded answer
+#[derive(Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The output matches the recorded answer
+    Pass,
+    /// The output does not match the recorded answer
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// There is no recorded answer for this part
+    Unrecorded,
+}
+
+/// Result of verifying a day
+#[derive(Debug, PartialEq, Eq)]
+pub enum DayOutcome {
+    /// The solution ran and both parts were compared
+    Checked([PartStatus; 2]),
+    /// The day could not be verified, but that is not a regression
+    Skipped(String),
+    /// The solution could not be run
+    Failed(String),
+}
+
+impl DayOutcome {
+    /// Whether the outcome should make the verification fail
+    pub fn is_regression(&self) -> bool {
+        match self {
+            DayOutcome::Checked(parts) => {
+                parts.iter().any(|p| matches!(p, PartStatus::Fail { .. }))
+            }
+            DayOutcome::Skipped(_) => false,
+            DayOutcome::Failed(_) => true,
+        }
+    }
+}
+
+/// Extracts the answers of both parts from the output of a solution
+/// using the first capture group of each pattern
+pub fn extract_answers(
+    patterns: &AnswerPatterns,
+    output: &str,
+) -> Result<[Option<String>; 2], regex::Error> {
+    let extract = |pattern: &str| -> Result<Option<String>, regex::Error> {
+        Ok(Regex::new(pattern)?
+            .captures(output)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().trim().to_string()))
+    };
+
+    Ok([extract(&patterns.part1)?, extract(&patterns.part2)?])
+}
+
+/// Compares the answers found in the output with the recorded ones
+pub fn compare_answers(recorded: &Answers, found: [Option<String>; 2]) -> [PartStatus; 2] {
+    let compare = |expected: Option<&String>, actual: Option<String>| match expected {
+        None => PartStatus::Unrecorded,
+        Some(expected) if actual.as_ref() == Some(expected) => PartStatus::Pass,
+        Some(expected) => PartStatus::Fail {
+            expected: expected.clone(),
+            actual,
+        },
+    };
+
+    let [part1, part2] = found;
+    [
+        compare(recorded.part(1), part1),
+        compare(recorded.part(2), part2),
+    ]
+}
+
+/// Runs the solution of a day with the `run` command of its language and
+/// returns the answers found in its output. `None` when there is no `run` command
+pub fn run_solution(
+    entry: &DayEntry,
+    registry: &TemplateRegistry,
+) -> Result<Option<[Option<String>; 2]>, String> {
+    let template = registry
+        .template_for_challenge(&entry.challenge())
+        .map_err(|e| format!("Unable to read the template: {}", e))?;
+
+    let Some(run) = template.run else {
+        return Ok(None);
+    };
+
+    let output = run_shell_command(&run.command, &entry.path)
+        .map_err(|e| format!("{} failed to execute: {}", run.command, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed to execute\n\n{}",
+            run.command,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let found = extract_answers(
+        &template.answer_patterns,
+        &String::from_utf8_lossy(&output.stdout),
+    )
+    .map_err(|e| format!("Invalid answer pattern: {}", e))?;
+
+    // Days with times keep when each part was first run
+    DayTimes::update(&entry.path, false, |times| {
+        times.record_runs(&found, Timestamp::now())
+    })
+    .map_err(|e| format!("Unable to record the first run: {}", e))?;
+
+    Ok(Some(found))
+}
+
+/// Runs the solution of a day with the `run` command of its language and
+/// compares the output with the answers recorded in the day directory
+pub fn verify_day(entry: &DayEntry, registry: &TemplateRegistry) -> DayOutcome {
+    let recorded = match Answers::load(&entry.path) {
+        Ok(answers) => answers,
+        Err(e) => return DayOutcome::Failed(format!("Unable to read the answers: {}", e)),
+    };
+
+    if recorded == Answers::default() {
+        return DayOutcome::Skipped("No recorded answers".into());
+    }
+
+    match run_solution(entry, registry) {
+        Ok(Some(found)) => DayOutcome::Checked(compare_answers(&recorded, found)),
+        Ok(None) => DayOutcome::Skipped(format!("No run command for {}", entry.language)),
+        Err(e) => DayOutcome::Failed(e),
+    }
+}
+
+fn part_cell(status: &PartStatus) -> &'static str {
+    match status {
+        PartStatus::Pass => "pass",
+        PartStatus::Fail { .. } => "FAIL",
+        PartStatus::Unrecorded => "-",
+    }
+}
+
+/// Prints the pass/fail matrix of the verified days, followed by the
+/// details of every failure
+pub fn print_report(results: &[(DayEntry, DayOutcome)]) {
+    println!(
+        "{:<6} {:<12} {:<4} {:<8} {:<8}",
+        "Year", "Language", "Day", "Part 1", "Part 2"
+    );
+
+    for (entry, outcome) in results {
+        let (part1, part2) = match outcome {
+            DayOutcome::Checked([p1, p2]) => (part_cell(p1), part_cell(p2)),
+            DayOutcome::Skipped(_) => ("skip", "skip"),
+            DayOutcome::Failed(_) => ("ERROR", "ERROR"),
+        };
+
+        println!(
+            "{:<6} {:<12} {:<4} {:<8} {:<8}",
+            entry.year,
+            entry.language,
+            format!("{:02}", entry.day),
+            part1,
+            part2
+        );
+    }
+
+    for (entry, outcome) in results {
+        let name = format!("{} {} day {:02}", entry.year, entry.language, entry.day);
+        match outcome {
+            DayOutcome::Checked(parts) => {
+                for (i, part) in parts.iter().enumerate() {
+                    if let PartStatus::Fail { expected, actual } = part {
+                        println!(
+                            "\n{} part {}: expected {}, got {}",
+                            name,
+                            i + 1,
+                            expected,
+                            actual.as_deref().unwrap_or("nothing")
+                        );
+                    }
+                }
+            }
+            DayOutcome::Failed(m) => println!("\n{}: {}", name, m),
+            DayOutcome::Skipped(_) => {}
+        }
+    }
+
+    let passed = results.iter().filter(|(_, o)| !o.is_regression()).count();
+    println!("\n{} of {} days without regressions", passed, results.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_answers_with_default_patterns() {
+        let output = "Parsing...\nPart 1: 24000\npart 2 = abc def \n";
+        let found = extract_answers(&AnswerPatterns::default(), output).unwrap();
+
+        assert_eq!(
+            found,
+            [Some("24000".to_string()), Some("abc def".to_string())]
+        );
+
+        let found = extract_answers(&AnswerPatterns::default(), "nothing here").unwrap();
+        assert_eq!(found, [None, None]);
+    }
+
+    #[test]
+    fn test_extract_answers_with_custom_patterns() {
+        let patterns = AnswerPatterns {
+            part1: r"first=(\d+)".into(),
+            part2: r"second=(\d+)".into(),
+        };
+        let found = extract_answers(&patterns, "first=1 second=2").unwrap();
+
+        assert_eq!(found, [Some("1".to_string()), Some("2".to_string())]);
+
+        let patterns = AnswerPatterns {
+            part1: "(".into(),
+            ..Default::default()
+        };
+        assert!(extract_answers(&patterns, "").is_err());
+    }
+
+    #[test]
+    fn test_compare_answers() {
+        let recorded = Answers {
+            part1: Some("1".into()),
+            part2: Some("2".into()),
+        };
+
+        let result = compare_answers(&recorded, [Some("1".into()), Some("3".into())]);
+        assert_eq!(result[0], PartStatus::Pass);
+        assert_eq!(
+            result[1],
+            PartStatus::Fail {
+                expected: "2".into(),
+                actual: Some("3".into())
+            }
+        );
+        assert!(DayOutcome::Checked(result).is_regression());
+
+        let recorded = Answers {
+            part1: Some("1".into()),
+            part2: None,
+        };
+        let result = compare_answers(&recorded, [Some("1".into()), None]);
+        assert_eq!(result, [PartStatus::Pass, PartStatus::Unrecorded]);
+        assert!(!DayOutcome::Checked(result).is_regression());
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Directories of build output and dependencies, never watched
+pub const IGNORED_DIRS: &[&str] = &[
+    ".git",
+    ".venv",
+    "__pycache__",
+    "dist-newstyle",
+    "node_modules",
+    "target",
+];
+
+/// How often the files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Modification time and size of every watched file
+pub type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+/// Every file of the directory, without the ignored directories
+pub fn snapshot(dir: &Path) -> io::Result<Snapshot> {
+    let mut files = Snapshot::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            // Files may be removed while they are listed
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                let ignored = entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| IGNORED_DIRS.contains(&name));
+                if !ignored {
+                    pending.push(path);
+                }
+            } else {
+                files.insert(path, (metadata.modified().ok(), metadata.len()));
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Files created, modified or removed between two snapshots
+pub fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed = after
+        .iter()
+        .filter(|(path, state)| before.get(*path) != Some(state))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<PathBuf>>();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    changed.sort();
+    changed
+}
+
+/// Watches the files of a directory by polling them
+pub struct Watcher {
+    dir: PathBuf,
+    snapshot: Snapshot,
+    debounce: Duration,
+}
+
+impl Watcher {
+    pub fn new(dir: &Path, debounce: Duration) -> io::Result<Self> {
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            snapshot: snapshot(dir)?,
+            debounce,
+        })
+    }
+
+    /// Forgets the changes since the last snapshot, like the build output of
+    /// the last run
+    pub fn refresh(&mut self) -> io::Result<()> {
+        self.snapshot = snapshot(&self.dir)?;
+        Ok(())
+    }
+
+    /// Blocks until some files change and then stay unchanged for the
+    /// debounce time, so a save of several files is a single change.
+    /// Returns the changed files
+    pub fn wait(&mut self) -> io::Result<Vec<PathBuf>> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let mut current = snapshot(&self.dir)?;
+            if current == self.snapshot {
+                continue;
+            }
+
+            loop {
+                thread::sleep(self.debounce);
+                let later = snapshot(&self.dir)?;
+                if later == current {
+                    break;
+                }
+                current = later;
+            }
+
+            let changed = changed_files(&self.snapshot, &current);
+            self.snapshot = current;
+            if !changed.is_empty() {
+                return Ok(changed);
+            }
+        }
+    }
+}
+
+/// Result of a run of the command
+#[derive(Debug)]
+pub struct RunReport {
+    pub status: ExitStatus,
+    pub elapsed: Duration,
+    /// Standard output of the command, which is also shown as it runs
+    pub stdout: String,
+}
+
+impl Display for RunReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let seconds = self.elapsed.as_secs_f64();
+        match self.status.code() {
+            _ if self.status.success() => write!(f, "PASS in {:.2}s", seconds),
+            Some(code) => write!(f, "FAIL with exit code {} in {:.2}s", code, seconds),
+            None => write!(f, "FAIL, terminated by a signal after {:.2}s", seconds),
+        }
+    }
+}
+
+/// Runs the command with `sh -c` inside the directory, showing its output
+pub fn run_command(command: &str, dir: &Path) -> io::Result<RunReport> {
+    let start = Instant::now();
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdout = vec![];
+    if let Some(mut output) = child.stdout.take() {
+        let mut terminal = io::stdout();
+        let mut buffer = [0; 4096];
+        loop {
+            let read = output.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            terminal.write_all(&buffer[..read])?;
+            terminal.flush()?;
+            stdout.extend_from_slice(&buffer[..read]);
+        }
+    }
+
+    Ok(RunReport {
+        status: child.wait()?,
+        elapsed: start.elapsed(),
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+    })
+}
+
+/// Clears the terminal and moves the cursor to the top
+pub fn clear_screen() {
+    print!("\x1B[2J\x1B[H");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_snapshot_ignores_build_output() {
+        let tmp = TempDir::new("testing_watch").unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("target/debug")).unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.join("target/debug/day"), "binary").unwrap();
+
+        let before = snapshot(dir).unwrap();
+        assert_eq!(
+            before.keys().collect::<Vec<_>>(),
+            vec![&dir.join("src/main.rs")]
+        );
+
+        fs::write(dir.join("src/main.rs"), "fn main() { todo!() }").unwrap();
+        fs::write(dir.join("input.txt"), "1abc2").unwrap();
+        fs::write(dir.join("target/debug/day"), "new binary").unwrap();
+
+        assert_eq!(
+            changed_files(&before, &snapshot(dir).unwrap()),
+            vec![dir.join("input.txt"), dir.join("src/main.rs")]
+        );
+    }
+
+    #[test]
+    fn test_run_command_reports_the_status() {
+        let tmp = TempDir::new("testing_watch").unwrap();
+
+        let report = run_command("echo 'Part 1: 142'", tmp.path()).unwrap();
+        assert!(report.to_string().starts_with("PASS in "));
+        assert_eq!(report.stdout, "Part 1: 142\n");
+
+        let report = run_command("exit 3", tmp.path()).unwrap();
+        assert!(report.to_string().starts_with("FAIL with exit code 3 in "));
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use dialoguer::{Confirm, MultiSelect, Password, Select};
+
+use crate::interfaces::{DayChallenge, ProgrammingTemplate};
+
+/// Last day of an advent
+pub const LAST_DAY: u8 = 25;
+
+/// Whether the user can answer prompts
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Prompts user to pick a language of the template
+pub fn select_language(languages: &[String], default: Option<&str>) -> io::Result<String> {
+    let default = default
+        .and_then(|d| languages.iter().position(|l| l == d))
+        .unwrap_or(0);
+
+    let selected = Select::new()
+        .with_prompt("Language")
+        .items(languages)
+        .default(default)
+        .interact()?;
+
+    Ok(languages[selected].clone())
+}
+
+/// Prompts user to pick the starter templates of a new repository
+pub fn select_starters(names: &[&str]) -> io::Result<Vec<usize>> {
+    MultiSelect::new()
+        .with_prompt("Languages (space to toggle)")
+        .items(names)
+        .interact()
+}
+
+/// Prompts user to pick a day. Days that already exist are marked
+pub fn select_day(existing: &[u8], default: u8) -> io::Result<u8> {
+    let items = (1..=LAST_DAY)
+        .map(|day| {
+            if existing.contains(&day) {
+                format!("Day {:02} (exists)", day)
+            } else {
+                format!("Day {:02}", day)
+            }
+        })
+        .collect::<Vec<String>>();
+
+    let selected = Select::new()
+        .with_prompt("Day")
+        .items(&items)
+        .default(usize::from(default.clamp(1, LAST_DAY) - 1))
+        .interact()?;
+
+    Ok(selected as u8 + 1)
+}
+
+/// Shows what is going to be created and asks user to continue
+pub fn confirm_plan(
+    challenge: &DayChallenge,
+    template: &ProgrammingTemplate,
+    path: &Path,
+) -> io::Result<bool> {
+    println!("\n{}", challenge);
+    println!("Directory: {}", path.display());
+
+    for (name, commands) in [
+        ("Init commands", &template.init_commands),
+        ("Commands", &template.commands),
+    ] {
+        if !commands.is_empty() {
+            println!("{}:", name);
+            for c in commands {
+                println!("  - {}", c.command);
+            }
+        }
+    }
+
+    if !template.folders.is_empty() {
+        println!("Folders: {}", template.folders.join(", "));
+    }
+
+    if !template.files.is_empty() {
+        let files = template
+            .files
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<&str>>();
+        println!("Files: {}", files.join(", "));
+    }
+
+    println!();
+
+    Confirm::new()
+        .with_prompt("Create this day?")
+        .default(true)
+        .interact()
+}
+
+/// Asks user a yes or no question
+pub fn confirm(prompt: &str) -> io::Result<bool> {
+    Confirm::new().with_prompt(prompt).default(false).interact()
+}
+
+/// Prompts user for a password, twice when it is a new one
+pub fn password(prompt: &str, new: bool) -> io::Result<String> {
+    let mut password = Password::new();
+    password.with_prompt(prompt);
+
+    if new {
+        password.with_confirmation("Repeat the password", "The passwords do not match");
+    }
+
+    password.interact()
+}
@@ -0,0 +1,646 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use regex::Regex;
+use toml_edit::{Array, DocumentMut, Item, Value};
+
+/// Days of `scaffold-year` are created in parallel, but the manifests are
+/// edited one at a time
+static EDITS: Mutex<()> = Mutex::new(());
+
+/// Error type for the workspace manifests
+#[derive(Debug)]
+pub enum WorkspaceError {
+    /// The manifest could not be read or written
+    IoError { path: PathBuf, source: io::Error },
+    /// The manifest could not be edited
+    BadManifest { path: PathBuf, message: String },
+}
+
+impl Display for WorkspaceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceError::IoError { path, .. } => {
+                write!(f, "Unable to access the manifest {}", path.display())
+            }
+            WorkspaceError::BadManifest { path, message } => {
+                write!(f, "Unable to edit {}: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WorkspaceError::IoError { source, .. } => Some(source),
+            WorkspaceError::BadManifest { .. } => None,
+        }
+    }
+}
+
+/// Kind of workspace that lists its members in a manifest at the root directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Workspace {
+    /// `members` of the `[workspace]` table of `Cargo.toml`
+    Cargo,
+    /// `use` directives of `go.work`
+    Go,
+    /// `packages` of `pnpm-workspace.yaml`
+    Pnpm,
+}
+
+impl Workspace {
+    pub const ALL: [Workspace; 3] = [Workspace::Cargo, Workspace::Go, Workspace::Pnpm];
+
+    /// Manifest of the workspace, at the root directory
+    pub fn manifest(self) -> &'static str {
+        match self {
+            Workspace::Cargo => "Cargo.toml",
+            Workspace::Go => "go.work",
+            Workspace::Pnpm => "pnpm-workspace.yaml",
+        }
+    }
+
+    /// Manifest of a day that makes it a member of the workspace
+    pub fn member_manifest(self) -> &'static str {
+        match self {
+            Workspace::Cargo => "Cargo.toml",
+            Workspace::Go => "go.mod",
+            Workspace::Pnpm => "package.json",
+        }
+    }
+
+    /// Members listed in the manifest, as written
+    pub fn members(self, content: &str) -> Result<Vec<String>, String> {
+        match self {
+            Workspace::Cargo => {
+                let doc = content.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+                Ok(cargo_list(&doc, "members"))
+            }
+            Workspace::Go => Ok(go_entries(content)
+                .into_iter()
+                .map(|(_, member)| member)
+                .collect()),
+            Workspace::Pnpm => Ok(pnpm_entries(content)?
+                .into_iter()
+                .map(|(_, member)| member)
+                .collect()),
+        }
+    }
+
+    /// Adds the member to the manifest. `None` when it is already a member,
+    /// or when the manifest is not a workspace
+    pub fn add_member(self, content: &str, member: &str) -> Result<Option<String>, String> {
+        match self {
+            Workspace::Cargo => cargo_add(content, member),
+            Workspace::Go => Ok(go_add(content, member)),
+            Workspace::Pnpm => pnpm_add(content, member),
+        }
+    }
+
+    /// Removes the member from the manifest. `None` when it is not listed
+    pub fn remove_member(self, content: &str, member: &str) -> Result<Option<String>, String> {
+        match self {
+            Workspace::Cargo => cargo_remove(content, member),
+            Workspace::Go => Ok(remove_lines(
+                content,
+                go_entries(content)
+                    .into_iter()
+                    .filter(|(_, m)| normalize(m) == member),
+            )),
+            Workspace::Pnpm => Ok(remove_lines(
+                content,
+                pnpm_entries(content)?
+                    .into_iter()
+                    .filter(|(_, m)| normalize(m) == member),
+            )),
+        }
+    }
+}
+
+/// Member as a path relative to the root, without `./` or a trailing `/`
+fn normalize(member: &str) -> &str {
+    let member = member.trim();
+    let member = member.strip_prefix("./").unwrap_or(member);
+    member.strip_suffix('/').unwrap_or(member)
+}
+
+/// Whether the member is the path or matches it as a glob like `src/*/rust/*`
+fn matches(pattern: &str, member: &str) -> bool {
+    let pattern = normalize(pattern);
+    if !pattern.contains(['*', '?']) {
+        return pattern == member;
+    }
+
+    let mut re = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+
+    Regex::new(&re).is_ok_and(|re| re.is_match(member))
+}
+
+/// Strings of an array of the `[workspace]` table
+fn cargo_list(doc: &DocumentMut, key: &str) -> Vec<String> {
+    doc.get("workspace")
+        .and_then(|w| w.get(key))
+        .and_then(Item::as_array)
+        .map(|a| {
+            a.iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn cargo_add(content: &str, member: &str) -> Result<Option<String>, String> {
+    let mut doc = content.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+
+    let listed = cargo_list(&doc, "members")
+        .iter()
+        .chain(&cargo_list(&doc, "exclude"))
+        .any(|m| matches(m, member));
+    let Some(workspace) = doc.get_mut("workspace").and_then(Item::as_table_like_mut) else {
+        return Ok(None);
+    };
+    if listed {
+        return Ok(None);
+    }
+
+    let members = workspace
+        .entry("members")
+        .or_insert(Item::Value(Value::Array(Array::new())))
+        .as_array_mut()
+        .ok_or("`members` is not an array")?;
+
+    // The new member is written like the last one, on its own line when they are
+    let last = members.iter().last().map(|last| {
+        let decor = last.decor();
+        let text = |raw: Option<&toml_edit::RawString>| {
+            raw.and_then(|r| r.as_str()).unwrap_or("").to_string()
+        };
+        (text(decor.prefix()), text(decor.suffix()))
+    });
+    let trailing = members.trailing().as_str().unwrap_or("").to_string();
+    members.push(member);
+
+    if let Some((prefix, suffix)) = last {
+        let count = members.len();
+        let mut prefix = if prefix.is_empty() {
+            " ".into()
+        } else {
+            prefix
+        };
+        // A comment after the last comma stays on the line of the last member
+        if let Some((comment, rest)) = trailing.rsplit_once('\n') {
+            prefix = format!("{}{}", comment, prefix);
+            members.set_trailing(format!("\n{}", rest));
+        }
+
+        if let Some(previous) = members.get_mut(count - 2) {
+            previous.decor_mut().set_suffix("");
+        }
+        if let Some(added) = members.get_mut(count - 1) {
+            added.decor_mut().set_prefix(prefix);
+            added.decor_mut().set_suffix(suffix);
+        }
+    }
+
+    Ok(Some(doc.to_string()))
+}
+
+fn cargo_remove(content: &str, member: &str) -> Result<Option<String>, String> {
+    let mut doc = content.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+
+    let Some(members) = doc
+        .get_mut("workspace")
+        .and_then(|w| w.get_mut("members"))
+        .and_then(Item::as_array_mut)
+    else {
+        return Ok(None);
+    };
+    let Some(index) = members
+        .iter()
+        .position(|m| m.as_str().is_some_and(|m| normalize(m) == member))
+    else {
+        return Ok(None);
+    };
+
+    let removed = members.remove(index);
+    let prefix = removed
+        .decor()
+        .prefix()
+        .and_then(|p| p.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    if index == 0 {
+        // The next member takes the place of the first one, without its leading space
+        if let Some(next) = members.get_mut(0) {
+            next.decor_mut().set_prefix(prefix);
+        }
+    } else if let Some((comment, _)) = prefix.split_once('\n') {
+        // A comment on the line of the previous member stays there
+        match members.get_mut(index) {
+            Some(next) => {
+                let next_prefix = next.decor().prefix().and_then(|p| p.as_str()).unwrap_or("");
+                let next_prefix = format!("{}{}", comment, next_prefix);
+                next.decor_mut().set_prefix(next_prefix);
+            }
+            None => {
+                let trailing = format!("{}{}", comment, members.trailing().as_str().unwrap_or(""));
+                members.set_trailing(trailing);
+            }
+        }
+    }
+
+    Ok(Some(doc.to_string()))
+}
+
+/// Value of a line without its comment and quotes
+fn unquote(value: &str) -> String {
+    let value = value.split(" #").next().unwrap_or(value);
+    let value = value.split("//").next().unwrap_or(value).trim();
+    value
+        .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+        .to_string()
+}
+
+/// Line and path of every `use` directive of a `go.work` file
+fn go_entries(content: &str) -> Vec<(usize, String)> {
+    let mut entries = vec![];
+    let mut in_block = false;
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if in_block {
+            if trimmed.starts_with(')') {
+                in_block = false;
+            } else if !trimmed.is_empty() && !trimmed.starts_with("//") {
+                entries.push((i, unquote(trimmed)));
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("use") {
+            let rest = rest.trim();
+            if rest.starts_with('(') {
+                in_block = true;
+            } else if !rest.is_empty() {
+                entries.push((i, unquote(rest)));
+            }
+        }
+    }
+
+    entries
+}
+
+fn go_add(content: &str, member: &str) -> Option<String> {
+    if go_entries(content)
+        .iter()
+        .any(|(_, m)| normalize(m) == member)
+    {
+        return None;
+    }
+
+    let mut lines = content.lines().map(String::from).collect::<Vec<String>>();
+    let block_end = lines
+        .iter()
+        .position(|l| l.trim().starts_with("use") && l.contains('('))
+        .and_then(|start| {
+            lines[start..]
+                .iter()
+                .position(|l| l.trim().starts_with(')'))
+                .map(|end| start + end)
+        });
+
+    match (block_end, go_entries(content).last()) {
+        (Some(end), _) => {
+            let indent = match lines[end - 1].trim_end() {
+                previous if previous.contains('(') => "\t".to_string(),
+                previous => previous[..previous.len() - previous.trim_start().len()].to_string(),
+            };
+            lines.insert(end, format!("{}./{}", indent, member));
+        }
+        (None, Some((last, _))) => lines.insert(last + 1, format!("use ./{}", member)),
+        (None, None) => {
+            if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("use ./{}", member));
+        }
+    }
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Line and path of every package of a `pnpm-workspace.yaml` file
+fn pnpm_entries(content: &str) -> Result<Vec<(usize, String)>, String> {
+    let mut entries = vec![];
+    let mut lines = content.lines().enumerate();
+
+    let Some((_, header)) = lines.find(|(_, l)| l.starts_with("packages:")) else {
+        return Ok(entries);
+    };
+    let inline = unquote(header.trim_start_matches("packages:"));
+    if !inline.is_empty() {
+        return Err("`packages` must be a list with one package per line".into());
+    }
+
+    for (i, line) in lines {
+        let trimmed = line.trim();
+        if let Some(package) = trimmed.strip_prefix('-') {
+            entries.push((i, unquote(package)));
+        } else if !trimmed.is_empty() && !trimmed.starts_with('#') && !line.starts_with(' ') {
+            break;
+        }
+    }
+
+    Ok(entries)
+}
+
+fn pnpm_add(content: &str, member: &str) -> Result<Option<String>, String> {
+    let entries = pnpm_entries(content)?;
+    if entries
+        .iter()
+        .any(|(_, p)| !p.starts_with('!') && matches(p, member))
+    {
+        return Ok(None);
+    }
+
+    let mut lines = content.lines().map(String::from).collect::<Vec<String>>();
+    match entries.last() {
+        // Written with the indentation and quotes of the last package
+        Some((last, _)) => {
+            let line = &lines[*last];
+            let dash = line.find('-').unwrap_or(0);
+            let value = line[dash + 1..].trim_start();
+            let item = &line[..line.len() - value.len()];
+            let quote = match value.chars().next() {
+                Some(q @ ('"' | '\'')) => q.to_string(),
+                _ => String::new(),
+            };
+            let new_line = format!("{}{}{}{}", item, quote, member, quote);
+            lines.insert(last + 1, new_line);
+        }
+        None => match lines.iter().position(|l| l.starts_with("packages:")) {
+            Some(header) => lines.insert(header + 1, format!("  - '{}'", member)),
+            None => {
+                lines.push("packages:".into());
+                lines.push(format!("  - '{}'", member));
+            }
+        },
+    }
+
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+/// Removes the lines of the entries. `None` when there are none
+fn remove_lines(content: &str, entries: impl Iterator<Item = (usize, String)>) -> Option<String> {
+    let remove = entries.map(|(i, _)| i).collect::<Vec<usize>>();
+    if remove.is_empty() {
+        return None;
+    }
+
+    let lines = content
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| !remove.contains(i))
+        .map(|(_, l)| l)
+        .collect::<Vec<&str>>();
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Path of the day relative to the root directory, with `/` separators
+fn member_path(root: &Path, day_path: &Path) -> Option<String> {
+    day_path
+        .strip_prefix(root)
+        .ok()
+        .and_then(|p| p.to_str())
+        .map(|p| p.replace('\\', "/"))
+}
+
+/// Applies the edit to every workspace manifest of the root directory.
+/// Returns the manifests that changed
+fn edit_manifests(
+    root: &Path,
+    day_path: &Path,
+    edit: impl Fn(Workspace, &str, &str) -> Result<Option<String>, String>,
+) -> Result<Vec<PathBuf>, WorkspaceError> {
+    let Some(member) = member_path(root, day_path) else {
+        return Ok(vec![]);
+    };
+    let _lock = EDITS.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut edited = vec![];
+    for workspace in Workspace::ALL {
+        let path = root.join(workspace.manifest());
+        if !path.is_file() {
+            continue;
+        }
+
+        let io_error = |e| WorkspaceError::IoError {
+            path: path.clone(),
+            source: e,
+        };
+        let content = fs::read_to_string(&path).map_err(io_error)?;
+        let updated =
+            edit(workspace, &content, &member).map_err(|message| WorkspaceError::BadManifest {
+                path: path.clone(),
+                message,
+            })?;
+
+        if let Some(updated) = updated {
+            fs::write(&path, updated).map_err(io_error)?;
+            edited.push(path);
+        }
+    }
+
+    Ok(edited)
+}
+
+/// Adds the day to the workspaces of the root directory it has a manifest for,
+/// like a `Cargo.toml` for a Cargo workspace. Returns the manifests that changed
+pub fn register_day(root: &Path, day_path: &Path) -> Result<Vec<PathBuf>, WorkspaceError> {
+    edit_manifests(root, day_path, |workspace, content, member| {
+        if !day_path.join(workspace.member_manifest()).is_file() {
+            return Ok(None);
+        }
+        workspace.add_member(content, member)
+    })
+}
+
+/// Removes the day from every workspace of the root directory. Returns the
+/// manifests that changed
+pub fn unregister_day(root: &Path, day_path: &Path) -> Result<Vec<PathBuf>, WorkspaceError> {
+    edit_manifests(root, day_path, |workspace, content, member| {
+        workspace.remove_member(content, member)
+    })
+}
+
+/// Manifests of the root directory that list the day as a member
+pub fn registered_manifests(root: &Path, day_path: &Path) -> Vec<PathBuf> {
+    let Some(member) = member_path(root, day_path) else {
+        return vec![];
+    };
+
+    Workspace::ALL
+        .into_iter()
+        .map(|workspace| (workspace, root.join(workspace.manifest())))
+        .filter(|(workspace, path)| {
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|content| workspace.members(&content).ok())
+                .is_some_and(|members| members.iter().any(|m| normalize(m) == member))
+        })
+        .map(|(_, path)| path)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    const MEMBER: &str = "src/2023/rust/Day-2-Cube";
+
+    #[test]
+    fn test_cargo_members_keep_their_format() {
+        let content = "\
+[workspace]
+resolver = \"2\"
+# Every day of the advent
+members = [
+    \"src/2023/rust/Day-1-Trebuchet\", # the first one
+]
+
+[workspace.dependencies]
+regex = \"1\"
+";
+        let added = Workspace::Cargo
+            .add_member(content, MEMBER)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            added,
+            content.replace(
+                "# the first one\n",
+                "# the first one\n    \"src/2023/rust/Day-2-Cube\",\n"
+            )
+        );
+        assert_eq!(Workspace::Cargo.add_member(&added, MEMBER).unwrap(), None);
+        assert_eq!(
+            Workspace::Cargo.remove_member(&added, MEMBER).unwrap(),
+            Some(content.to_string())
+        );
+
+        let inline = "[workspace]\nmembers = [\"tools\"]\n";
+        assert_eq!(
+            Workspace::Cargo
+                .add_member(inline, MEMBER)
+                .unwrap()
+                .unwrap(),
+            "[workspace]\nmembers = [\"tools\", \"src/2023/rust/Day-2-Cube\"]\n"
+        );
+
+        // Globs and excluded days are left as they are, and so are packages
+        let glob = "[workspace]\nmembers = [\"src/*/rust/*\"]\n";
+        assert_eq!(Workspace::Cargo.add_member(glob, MEMBER).unwrap(), None);
+        let package = "[package]\nname = \"aoc\"\n";
+        assert_eq!(Workspace::Cargo.add_member(package, MEMBER).unwrap(), None);
+    }
+
+    #[test]
+    fn test_go_work_uses() {
+        let content = "go 1.21\n\nuse (\n\t./tools\n)\n";
+        let added = Workspace::Go.add_member(content, MEMBER).unwrap().unwrap();
+        assert_eq!(
+            added,
+            "go 1.21\n\nuse (\n\t./tools\n\t./src/2023/rust/Day-2-Cube\n)\n"
+        );
+        assert_eq!(
+            Workspace::Go.remove_member(&added, MEMBER).unwrap(),
+            Some(content.to_string())
+        );
+
+        assert_eq!(
+            Workspace::Go
+                .add_member("go 1.21\n", MEMBER)
+                .unwrap()
+                .unwrap(),
+            "go 1.21\n\nuse ./src/2023/rust/Day-2-Cube\n"
+        );
+    }
+
+    #[test]
+    fn test_pnpm_packages() {
+        let content = "packages:\n  - 'tools/*'\n  - \"src/2023/rust/Day-1-Trebuchet\"\n";
+        let added = Workspace::Pnpm
+            .add_member(content, MEMBER)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            added,
+            format!("{}  - \"src/2023/rust/Day-2-Cube\"\n", content)
+        );
+        assert_eq!(
+            Workspace::Pnpm.remove_member(&added, MEMBER).unwrap(),
+            Some(content.to_string())
+        );
+        assert_eq!(
+            Workspace::Pnpm
+                .add_member("packages:\n  - 'src/**'\n", MEMBER)
+                .unwrap(),
+            None
+        );
+        assert!(Workspace::Pnpm
+            .add_member("packages: ['tools']\n", MEMBER)
+            .is_err());
+    }
+
+    #[test]
+    fn test_register_days_with_a_manifest() {
+        let tmp = TempDir::new("testing_workspace").unwrap();
+        let root = tmp.path();
+        let day = root.join(MEMBER);
+        fs::create_dir_all(&day).unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        fs::write(root.join("go.work"), "go 1.21\n").unwrap();
+
+        // Only the workspaces the day has a manifest for
+        fs::write(day.join("Cargo.toml"), "[package]\nname = \"day\"\n").unwrap();
+        assert_eq!(
+            register_day(root, &day).unwrap(),
+            vec![root.join("Cargo.toml")]
+        );
+        assert_eq!(
+            registered_manifests(root, &day),
+            vec![root.join("Cargo.toml")]
+        );
+        assert!(register_day(root, &day).unwrap().is_empty());
+
+        assert_eq!(
+            unregister_day(root, &day).unwrap(),
+            vec![root.join("Cargo.toml")]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            "[workspace]\nmembers = []\n"
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use serde_yaml::{from_str, Value};
+use std::fmt::{self, Display, Formatter};
 use tera::Tera;
 
-use crate::interfaces::*;
+use crate::{interfaces::*, requires::Requirement, utils::edit_distance};
+
+/// Top-level keys of the template file that are not languages
+pub const RESERVED_KEYS: &[&str] = &["hooks"];
 
 /// Error type for the yaml parser
 #[derive(Debug)]
@@ -9,10 +13,47 @@ pub enum YamlParserError {
     TeraError(tera::Error),
     YamlError(serde_yaml::Error),
     NoLanguagesProvided,
-    NoLanguageFound(String),
+    NoLanguageFound {
+        language: String,
+        /// Closest languages and aliases of the template
+        suggestions: Vec<String>,
+    },
     BadFormat(String),
 }
 
+impl Display for YamlParserError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            YamlParserError::TeraError(_) => write!(f, "Unable to render the template file"),
+            YamlParserError::YamlError(_) => write!(f, "The template file is not valid yaml"),
+            YamlParserError::NoLanguagesProvided => {
+                write!(f, "No languages provided in the template file")
+            }
+            YamlParserError::NoLanguageFound {
+                language,
+                suggestions,
+            } => {
+                write!(f, "No language found for {} in template file", language)?;
+                if !suggestions.is_empty() {
+                    write!(f, ". Did you mean {}?", suggestions.join(", "))?;
+                }
+                Ok(())
+            }
+            YamlParserError::BadFormat(m) => write!(f, "Bad format! {}", m),
+        }
+    }
+}
+
+impl std::error::Error for YamlParserError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            YamlParserError::TeraError(e) => Some(e),
+            YamlParserError::YamlError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 /// Populates the yml file with the given metadata
 pub fn populate_yml(
     yml_file_content: &str,
@@ -36,8 +77,8 @@ pub fn populate_yml(
     }
 
     let rendered = tera
-        .render_str(&yml_file_content, &context)
-        .map_err(|e| YamlParserError::TeraError(e))?;
+        .render_str(yml_file_content, &context)
+        .map_err(YamlParserError::TeraError)?;
 
     Ok(rendered)
 }
@@ -55,26 +96,35 @@ pub fn parse_values_yml(
         .as_mapping()
         .ok_or_else(|| YamlParserError::NoLanguagesProvided)?;
 
+    let languages = available_langs
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str()?, value)))
+        .filter(|(name, _)| !RESERVED_KEYS.contains(name))
+        .map(|(name, value)| Ok((name.to_string(), aliases_of(name, value)?)))
+        .collect::<Result<Vec<_>, YamlParserError>>()?;
+    let language = resolve_language(language, &languages)?;
+
     let target_lan_mapping = available_langs
-        .get(&Value::String(language.to_string()))
-        .ok_or(YamlParserError::NoLanguageFound(language.to_string()))?;
+        .get(Value::String(language.clone()))
+        .ok_or_else(|| YamlParserError::NoLanguagesProvided)?;
 
-    template.language = language.into();
+    template.aliases = aliases_of(&language, target_lan_mapping)?;
+    template.language = language;
 
     // Init commands
     value_to_list_commands(
-        target_lan_mapping.get(&Value::String("commands".into())),
+        target_lan_mapping.get(Value::String("commands".into())),
         &mut template.commands,
     )?;
 
     // Init commands
     value_to_list_commands(
-        target_lan_mapping.get(&Value::String("init_commands".into())),
+        target_lan_mapping.get(Value::String("init_commands".into())),
         &mut template.init_commands,
     )?;
 
     // Folders
-    if let Some(folders) = target_lan_mapping.get(&Value::String("folders".into())) {
+    if let Some(folders) = target_lan_mapping.get(Value::String("folders".into())) {
         template.folders = folders
             .as_sequence()
             .ok_or_else(|| {
@@ -93,28 +143,255 @@ pub fn parse_values_yml(
             .collect::<Result<Vec<String>, YamlParserError>>()?;
     }
 
+    // Required tools
+    if let Some(requires) = target_lan_mapping.get(Value::String("requires".into())) {
+        template.requires = requires
+            .as_sequence()
+            .ok_or_else(|| {
+                YamlParserError::BadFormat("requires must be in a list. Check your format".into())
+            })?
+            .iter()
+            .map(|tool| {
+                tool.as_str()
+                    .ok_or_else(|| {
+                        YamlParserError::BadFormat(
+                            "Each required tool must be a single string. Check your format".into(),
+                        )
+                    })?
+                    .parse()
+                    .map_err(YamlParserError::BadFormat)
+            })
+            .collect::<Result<Vec<Requirement>, YamlParserError>>()?;
+    }
+
     // Files
-    if let Some(files) = target_lan_mapping.get(&Value::String("files".into())) {
+    if let Some(files) = target_lan_mapping.get(Value::String("files".into())) {
         template.files = files
             .as_sequence()
             .ok_or_else(|| {
                 YamlParserError::BadFormat("files must be in a list. Check your format".into())
             })?
             .iter()
-            .map(|file| File::try_from(file))
+            .map(File::try_from)
             .collect::<Result<Vec<File>, YamlParserError>>()?;
     }
 
+    // Run command
+    if let Some(run) = target_lan_mapping.get(Value::String("run".into())) {
+        template.run = Some(Command::try_from(run)?);
+    }
+
+    // Test command
+    if let Some(test) = target_lan_mapping.get(Value::String("test".into())) {
+        template.test = Some(Command::try_from(test)?);
+    }
+
+    // Entry file
+    if let Some(entry) = target_lan_mapping.get(Value::String("entry".into())) {
+        let entry = entry.as_str().ok_or_else(|| {
+            YamlParserError::BadFormat("entry must be the path of a file. Check your format".into())
+        })?;
+        template.entry = Some(entry.to_string());
+    }
+
+    // Hooks
+    template.hooks = hooks_of(target_lan_mapping)?;
+
+    // Answer patterns
+    if let Some(answers) = target_lan_mapping.get(Value::String("answers".into())) {
+        let answers = answers.as_mapping().ok_or_else(|| {
+            YamlParserError::BadFormat("answers must be a map. Check your format".into())
+        })?;
+
+        for (part, pattern) in [
+            ("part1", &mut template.answer_patterns.part1),
+            ("part2", &mut template.answer_patterns.part2),
+        ] {
+            if let Some(value) = answers.get(Value::String(part.into())) {
+                *pattern = value
+                    .as_str()
+                    .ok_or_else(|| {
+                        YamlParserError::BadFormat(format!(
+                            "The {} answer pattern must be a single string. Check your format",
+                            part
+                        ))
+                    })?
+                    .to_string();
+            }
+        }
+    }
+
     Ok(template)
 }
 
+/// Lists the languages defined in the yml file, in the order they are written
+pub fn list_languages(yml_file_content: &str) -> Result<Vec<String>, YamlParserError> {
+    Ok(list_language_aliases(yml_file_content)?
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect())
+}
+
+/// Lists the languages defined in the yml file with their aliases
+pub fn list_language_aliases(
+    yml_file_content: &str,
+) -> Result<Vec<(String, Vec<String>)>, YamlParserError> {
+    // Variables are not known yet, so they are rendered empty
+    let placeholder = DayChallenge::new(1, 2015, String::new(), String::new());
+    let populated = populate_yml(yml_file_content, &placeholder)?;
+    let parsed_value: Value = from_str(&populated).map_err(YamlParserError::YamlError)?;
+
+    let mapping = parsed_value
+        .as_mapping()
+        .ok_or(YamlParserError::NoLanguagesProvided)?;
+    // Reserved keys like `hooks` are not languages
+    let languages = mapping
+        .iter()
+        .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v)))
+        .filter(|(name, _)| !RESERVED_KEYS.contains(&name.as_str()))
+        .map(|(name, value)| {
+            let aliases = aliases_of(&name, value)?;
+            Ok((name, aliases))
+        })
+        .collect::<Result<Vec<_>, YamlParserError>>()?;
+
+    // Only a file without any key is an error. A file with only reserved keys
+    // lists no languages, so its hooks apply to the languages of other files
+    if mapping.is_empty() {
+        return Err(YamlParserError::NoLanguagesProvided);
+    }
+
+    Ok(languages)
+}
+
+/// Reads the `aliases` of a language
+fn aliases_of(name: &str, language: &Value) -> Result<Vec<String>, YamlParserError> {
+    let Some(aliases) = language.get(Value::String("aliases".into())) else {
+        return Ok(vec![]);
+    };
+
+    aliases
+        .as_sequence()
+        .ok_or_else(|| {
+            YamlParserError::BadFormat(format!(
+                "aliases of {} must be in a list. Check your format",
+                name
+            ))
+        })?
+        .iter()
+        .map(|alias| {
+            alias.as_str().map(String::from).ok_or_else(|| {
+                YamlParserError::BadFormat(format!(
+                    "Each alias of {} must be a single string. Check your format",
+                    name
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Finds the name of a language from its name or one of its aliases, ignoring
+/// the case. When there is no match, the closest names are suggested
+pub fn resolve_language(
+    language: &str,
+    languages: &[(String, Vec<String>)],
+) -> Result<String, YamlParserError> {
+    let wanted = language.to_lowercase();
+
+    if let Some((name, _)) = languages.iter().find(|(name, aliases)| {
+        name.to_lowercase() == wanted || aliases.iter().any(|a| a.to_lowercase() == wanted)
+    }) {
+        return Ok(name.clone());
+    }
+
+    // Typos of a third of the word are still suggested
+    let max_distance = wanted.chars().count().div_ceil(3).max(1);
+    let mut candidates = languages
+        .iter()
+        .flat_map(|(name, aliases)| {
+            std::iter::once(name)
+                .chain(aliases)
+                .map(move |candidate| (candidate.to_lowercase(), name))
+        })
+        .filter_map(|(candidate, name)| {
+            let distance = edit_distance(&wanted, &candidate);
+            let is_prefix = wanted.len() > 1 && candidate.starts_with(&wanted);
+            (distance <= max_distance || is_prefix).then_some((distance, name.clone()))
+        })
+        .collect::<Vec<(usize, String)>>();
+    candidates.sort();
+
+    let mut suggestions: Vec<String> = vec![];
+    for (_, name) in candidates {
+        if !suggestions.contains(&name) {
+            suggestions.push(name);
+        }
+    }
+    suggestions.truncate(3);
+
+    Err(YamlParserError::NoLanguageFound {
+        language: language.to_string(),
+        suggestions,
+    })
+}
+
+/// Populates the yml file with the challenge and parses the template of its language
+pub fn template_for_challenge(
+    yml_file_content: &str,
+    day_challenge: &DayChallenge,
+) -> Result<ProgrammingTemplate, YamlParserError> {
+    let populated = populate_yml(yml_file_content, day_challenge)?;
+    parse_values_yml(&populated, &day_challenge.language)
+}
+
+/// Reads the `hooks` of a language or of the whole template file
+fn hooks_of(value: &Value) -> Result<Hooks, YamlParserError> {
+    let mut hooks = Hooks::default();
+
+    let Some(mapping) = value.get(Value::String("hooks".into())) else {
+        return Ok(hooks);
+    };
+    let mapping = mapping.as_mapping().ok_or_else(|| {
+        YamlParserError::BadFormat("hooks must be a map. Check your format".into())
+    })?;
+
+    for (name, commands) in mapping {
+        let commands_vec = match name.as_str() {
+            Some("pre_add") => &mut hooks.pre_add,
+            Some("post_add") => &mut hooks.post_add,
+            Some("pre_remove") => &mut hooks.pre_remove,
+            Some("post_remove") => &mut hooks.post_remove,
+            _ => {
+                return Err(YamlParserError::BadFormat(format!(
+                    "Unknown hook {:?}. Use pre_add, post_add, pre_remove or post_remove",
+                    name.as_str().unwrap_or_default()
+                )))
+            }
+        };
+        value_to_list_commands(Some(commands), commands_vec)?;
+    }
+
+    Ok(hooks)
+}
+
+/// Renders the yml file and reads its top-level `hooks`, shared by every language
+pub fn global_hooks(
+    yml_file_content: &str,
+    day_challenge: &DayChallenge,
+) -> Result<Hooks, YamlParserError> {
+    let populated = populate_yml(yml_file_content, day_challenge)?;
+    let parsed_value: Value = from_str(&populated).map_err(YamlParserError::YamlError)?;
+
+    hooks_of(&parsed_value)
+}
+
 /// Takes a yaml value and returns a vector of commands
 /// If the commands is not according to the tempalte, it will return an error
 fn value_to_list_commands(
     values: Option<&Value>,
     template_command_vec: &mut Vec<Command>,
 ) -> Result<(), YamlParserError> {
-    if let None = values {
+    if values.is_none() {
         return Ok(());
     }
 
@@ -124,7 +401,7 @@ fn value_to_list_commands(
 
     *template_command_vec = commands_sequence
         .iter()
-        .map(|command| Command::try_from(command))
+        .map(Command::try_from)
         .collect::<Result<Vec<Command>, YamlParserError>>()?;
     Ok(())
 }
@@ -139,7 +416,7 @@ impl TryFrom<&Value> for File {
 
         if let Some(file_map) = value.as_mapping() {
             let name = file_map
-                .get(&Value::String("name".into()))
+                .get(Value::String("name".into()))
                 .ok_or_else(|| {
                     YamlParserError::BadFormat(
                         "Each file must have a name. Check your format".into(),
@@ -152,7 +429,7 @@ impl TryFrom<&Value> for File {
                     )
                 })?;
 
-            let content = file_map.get(&Value::String("content".into()));
+            let content = file_map.get(Value::String("content".into()));
 
             if let Some(content) = content {
                 let content_str = content.as_str().ok_or_else(|| {
@@ -217,7 +494,7 @@ rust:
     - docs/
 ";
 
-        let template = parse_values_yml(&file_content, "rust").unwrap();
+        let template = parse_values_yml(file_content, "rust").unwrap();
 
         let expected = ProgrammingTemplate {
             language: "rust".to_string(),
@@ -230,11 +507,150 @@ rust:
                 File::empty("demo-input.txt"),
             ],
             folders: vec!["docs/".to_string()],
+            ..Default::default()
         };
 
         assert_eq!(template, expected, "Expected template to be equal");
     }
 
+    #[test]
+    fn test_parse_run_and_answer_patterns() {
+        let file_content = "
+rust:
+  run: cargo run -q --release
+  test: cargo test -q
+  entry: src/main.rs
+  answers:
+    part1: 'first: (\\d+)'
+";
+
+        let template = parse_values_yml(file_content, "rust").unwrap();
+
+        assert_eq!(
+            template.run,
+            Some(Command::new("cargo run -q --release", vec![]))
+        );
+        assert_eq!(template.test, Some(Command::new("cargo test -q", vec![])));
+        assert_eq!(template.entry.as_deref(), Some("src/main.rs"));
+        assert_eq!(template.answer_patterns.part1, "first: (\\d+)");
+        assert_eq!(
+            template.answer_patterns.part2,
+            AnswerPatterns::default().part2,
+            "Expected the default pattern for part 2"
+        );
+    }
+
+    #[test]
+    fn test_parse_hooks() {
+        let file_content = "
+hooks:
+  pre_add:
+    - echo {{ year }}
+rust:
+  hooks:
+    post_add:
+      - cargo fmt
+    pre_remove: [echo bye]
+";
+        let challenge = DayChallenge::new(1, 2023, "rust".into(), "title".into());
+
+        let template = parse_values_yml(file_content, "rust").unwrap();
+        assert_eq!(
+            template.hooks.post_add,
+            vec![Command::new("cargo fmt", vec![])]
+        );
+        assert_eq!(
+            template.hooks.pre_remove,
+            vec![Command::new("echo bye", vec![])]
+        );
+        assert!(template.hooks.pre_add.is_empty());
+
+        assert_eq!(
+            global_hooks(file_content, &challenge).unwrap().pre_add,
+            vec![Command::new("echo 2023", vec![])]
+        );
+        assert_eq!(list_languages(file_content).unwrap(), vec!["rust"]);
+        assert!(matches!(
+            parse_values_yml("rust:\n  hooks:\n    after_add: [ls]\n", "rust").unwrap_err(),
+            YamlParserError::BadFormat(_)
+        ));
+    }
+
+    #[test]
+    fn test_list_languages() {
+        let file_content = "
+rust:
+  commands:
+    - cargo new {{title}}
+python:
+  files:
+    - main.py
+";
+
+        assert_eq!(
+            list_languages(file_content).unwrap(),
+            vec!["rust", "python"]
+        );
+        assert!(matches!(
+            list_languages("").unwrap_err(),
+            YamlParserError::NoLanguagesProvided
+        ));
+    }
+
+    #[test]
+    fn test_languages_match_aliases_ignoring_case() {
+        let file_content = "
+python:
+  aliases: [py, Python3]
+  run: python3 main.py
+typescript:
+  aliases:
+    - ts
+";
+
+        for name in ["python", "PY", "python3", "Python"] {
+            let template = parse_values_yml(file_content, name).unwrap();
+            assert_eq!(template.language, "python");
+            assert_eq!(template.aliases, vec!["py", "Python3"]);
+        }
+
+        assert_eq!(
+            list_language_aliases(file_content).unwrap(),
+            vec![
+                (
+                    "python".to_string(),
+                    vec!["py".to_string(), "Python3".to_string()]
+                ),
+                ("typescript".to_string(), vec!["ts".to_string()]),
+            ]
+        );
+
+        match parse_values_yml(file_content, "pyhton").unwrap_err() {
+            YamlParserError::NoLanguageFound { suggestions, .. } => {
+                assert_eq!(suggestions, vec!["python"])
+            }
+            e => panic!("Expected not to find the language. Got {:?}", e),
+        }
+
+        let err = parse_values_yml(file_content, "type").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No language found for type in template file. Did you mean typescript?"
+        );
+
+        // A broken language is reported instead of being left out
+        let broken = format!("{}go:\n  aliases:\n    golang: true\n", file_content);
+        let err = parse_values_yml(&broken, "python").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bad format! aliases of go must be in a list. Check your format"
+        );
+        assert!(list_language_aliases("rust:\n  aliases: [[rs]]\n")
+            .unwrap_err()
+            .to_string()
+            .contains("Each alias of rust"));
+    }
+
     #[test]
     fn test_parse_yml_file_without_variables() {
         let metadata = DayChallenge::new(1, 2020, "Rust".to_string(), "test-2-numbers".into());
@@ -273,7 +689,7 @@ rust:
     - docs/
 ";
 
-        let parsed_content = populate_yml(&file_content, &metadata).unwrap();
+        let parsed_content = populate_yml(file_content, &metadata).unwrap();
 
         assert_eq!(&parsed_content, &expected);
     }
@@ -321,7 +737,7 @@ rust:
             &metadata.title, &metadata.year, &metadata.day, &metadata.language, &metadata.day
         );
 
-        let parsed_content = populate_yml(&file_content, &metadata).unwrap();
+        let parsed_content = populate_yml(file_content, &metadata).unwrap();
 
         assert_eq!(&parsed_content, &expected);
     }
@@ -329,7 +745,7 @@ rust:
     #[test]
     fn test_error_when_bad_formating() {
         let file_content = "";
-        let err = parse_values_yml(&file_content, "rust").unwrap_err();
+        let err = parse_values_yml(file_content, "rust").unwrap_err();
         assert!(
             matches!(err, YamlParserError::NoLanguagesProvided),
             "Expected error when file is empty. Got {:?}",
@@ -353,12 +769,12 @@ cosa:
   folders: 
     - docs/
 ";
-        let err = parse_values_yml(&file_content, "rust").unwrap_err();
+        let err = parse_values_yml(file_content, "rust").unwrap_err();
 
         assert!(
             matches!(
-                parse_values_yml(&file_content, "rust").unwrap_err(),
-                YamlParserError::NoLanguageFound(_)
+                parse_values_yml(file_content, "rust").unwrap_err(),
+                YamlParserError::NoLanguageFound { .. }
             ),
             "Expected not to find language. Got {:?}",
             err,
@@ -384,7 +800,7 @@ rust:
 
         assert!(
             matches!(
-                parse_values_yml(&file_content, "rust").unwrap_err(),
+                parse_values_yml(file_content, "rust").unwrap_err(),
                 YamlParserError::BadFormat(_)
             ),
             "Expected error with files . Got {:?}",